@@ -0,0 +1,57 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use juxt_adler32::paths;
+use juxt_adler32::Adler32;
+
+///
+/// the per byte modulo loop, as the baseline
+///
+fn naive(bytes: &[u8]) -> u32 {
+    let mut lo = 1;
+    let mut hi = 0;
+
+    for v in bytes.iter() {
+        lo = (lo + *v as u32) % 65521;
+        hi = (hi + lo) % 65521;
+    }
+
+    (hi << 16) + lo
+}
+
+fn throughput<T, F: Fn(&[u8]) -> T>(name: &str, bytes: &[u8], rounds: usize, f: F) -> T {
+    let mut adler = f(black_box(bytes));
+
+    let now = Instant::now();
+    for _ in 0..rounds {
+        adler = f(black_box(bytes));
+    }
+    let secs = now.elapsed().as_secs_f64();
+
+    println!("{:>8}: {:>10.2} MiB/s", name, (bytes.len() * rounds) as f64 / secs / 1024.0 / 1024.0);
+
+    adler
+}
+
+fn main() {
+    let bytes: Vec<u8> = (0..16 * 1024 * 1024u32).map(|v| (v * 31 + v / 7) as u8).collect();
+
+    let expected = throughput("naive", &bytes, 4, naive);
+    let adler32 = throughput("adler32", &bytes, 64, Adler32::from_bytes);
+    assert_eq!(adler32, expected);
+
+    let scalar = throughput("scalar", &bytes, 16, |bytes| paths::update_scalar(1, bytes));
+    assert_eq!(scalar, expected);
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("ssse3") {
+            let ssse3 = throughput("ssse3", &bytes, 64, |bytes| unsafe { paths::update_ssse3(1, bytes) });
+            assert_eq!(ssse3, expected);
+        }
+        if is_x86_feature_detected!("avx2") {
+            let avx2 = throughput("avx2", &bytes, 64, |bytes| unsafe { paths::update_avx2(1, bytes) });
+            assert_eq!(avx2, expected);
+        }
+    }
+}
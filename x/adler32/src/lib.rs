@@ -1,7 +1,13 @@
 use std::error::Error;
 use std::fmt::Display;
+use std::hash::BuildHasherDefault;
+use std::hash::Hasher;
+use std::io::Write;
 use std::str::FromStr;
 
+///
+/// the checksum, a plain value, it does not implement `Hasher`, feed the bytes through `Adler32Hasher`
+///
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Adler32(u32);
 
@@ -17,6 +23,18 @@ impl PartialEq<u32> for Adler32 {
     }
 }
 
+impl From<u32> for Adler32 {
+    fn from(value: u32) -> Self {
+        Adler32(value)
+    }
+}
+
+impl From<Adler32> for u32 {
+    fn from(value: Adler32) -> Self {
+        value.0
+    }
+}
+
 impl FromStr for Adler32 {
     type Err = Box<dyn Error>;
 
@@ -29,20 +47,361 @@ impl Adler32 {
     pub fn from_bytes(bytes: &[u8]) -> Self {
         Adler32(imp::calc(bytes))
     }
+
+    ///
+    /// checksum of `x ++ y` from `a = adler32(x)`, `b = adler32(y)` and `len_b = y.len()`, same as zlib's `adler32_combine`
+    ///
+    pub fn combine(a: Adler32, b: Adler32, len_b: u64) -> Adler32 {
+        Adler32(imp::combine(a.0, b.0, len_b))
+    }
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Adler32(1)
+    }
+}
+
+///
+/// incremental adler32, can be resumed from a saved `Adler32`
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Adler32Hasher(Adler32);
+
+impl From<Adler32> for Adler32Hasher {
+    fn from(value: Adler32) -> Self {
+        Self::resume(value)
+    }
+}
+
+impl Adler32Hasher {
+    pub fn new() -> Self {
+        Self(Adler32::default())
+    }
+
+    ///
+    /// continue from the checksum of the bytes already fed
+    ///
+    pub fn resume(adler32: Adler32) -> Self {
+        Self(adler32)
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.0 .0 = imp::update(self.0 .0, bytes);
+    }
+
+    pub fn finalize(self) -> Adler32 {
+        self.0
+    }
+}
+
+///
+/// the checksum as is, widened
+///
+impl Hasher for Adler32Hasher {
+    fn finish(&self) -> u64 {
+        self.0 .0 as u64
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+}
+
+pub type BuildAdler32 = BuildHasherDefault<Adler32Hasher>;
+
+impl Write for Adler32Hasher {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+///
+/// rolling adler32 over a fixed size window, rsync style
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Adler32Rolling {
+    adler: u32,
+    window_len: u32,
+}
+
+impl Adler32Rolling {
+    pub fn new(window: &[u8]) -> Self {
+        Self {
+            adler: imp::calc(window),
+            window_len: (window.len() % imp::BASE as usize) as u32,
+        }
+    }
+
+    ///
+    /// slide the window one byte, `out_byte` leaves from the front and `in_byte` enters at the back
+    ///
+    pub fn roll(&mut self, out_byte: u8, in_byte: u8) {
+        self.adler = imp::roll(self.adler, self.window_len, out_byte, in_byte);
+    }
+
+    pub fn value(&self) -> Adler32 {
+        Adler32(self.adler)
+    }
+}
+
+#[cfg(feature = "digest")]
+mod digest_impl {
+    use digest::consts::U4;
+    use digest::FixedOutput;
+    use digest::FixedOutputReset;
+    use digest::HashMarker;
+    use digest::Output;
+    use digest::OutputSizeUser;
+    use digest::Reset;
+    use digest::Update;
+
+    use super::Adler32Hasher;
+
+    impl HashMarker for Adler32Hasher {}
+
+    impl OutputSizeUser for Adler32Hasher {
+        type OutputSize = U4;
+    }
+
+    impl Update for Adler32Hasher {
+        fn update(&mut self, data: &[u8]) {
+            Adler32Hasher::update(self, data);
+        }
+    }
+
+    ///
+    /// big endian, same as the zlib trailer
+    ///
+    impl FixedOutput for Adler32Hasher {
+        fn finalize_into(self, out: &mut Output<Self>) {
+            out.copy_from_slice(&Adler32Hasher::finalize(self).0.to_be_bytes());
+        }
+    }
+
+    impl Reset for Adler32Hasher {
+        fn reset(&mut self) {
+            *self = Self::new();
+        }
+    }
+
+    impl FixedOutputReset for Adler32Hasher {
+        fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+            out.copy_from_slice(&std::mem::take(self).finalize().0.to_be_bytes());
+        }
+    }
+}
+
+///
+/// each path of the update alone, for `benches/throughput.rs` only, not a part of the api
+///
+#[doc(hidden)]
+pub mod paths {
+    pub use crate::imp::update_scalar;
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    pub use crate::imp::x86::update_avx2;
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    pub use crate::imp::x86::update_ssse3;
 }
 
 mod imp {
-    const BASE: u32 = 65521;
+    pub const BASE: u32 = 65521;
 
     pub fn calc(bytes: &[u8]) -> u32 {
-        let mut lo = 1 & 0xffff;
-        let mut hi = (1 >> 16) & 0xffff;
+        update(1, bytes)
+    }
 
-        for v in bytes.iter() {
-            lo = (lo + *v as u32) % BASE;
-            hi = (hi + lo) % BASE;
+    ///
+    /// the largest n such that `255 * n * (n + 1) / 2 + (n + 1) * (BASE - 1) <= 2^32 - 1`
+    ///
+    pub const NMAX: usize = 5552;
+
+    pub fn update(adler: u32, bytes: &[u8]) -> u32 {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                return unsafe { x86::update_avx2(adler, bytes) };
+            }
+            if is_x86_feature_detected!("ssse3") {
+                return unsafe { x86::update_ssse3(adler, bytes) };
+            }
+        }
+
+        update_scalar(adler, bytes)
+    }
+
+    ///
+    /// defer the modulo to every `NMAX` bytes
+    ///
+    pub fn update_scalar(adler: u32, bytes: &[u8]) -> u32 {
+        let mut lo = adler & 0xffff;
+        let mut hi = (adler >> 16) & 0xffff;
+
+        for chunk in bytes.chunks(NMAX) {
+            for v in chunk.iter() {
+                lo += *v as u32;
+                hi += lo;
+            }
+            lo %= BASE;
+            hi %= BASE;
+        }
+
+        (hi << 16) + lo
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    pub mod x86 {
+        #[cfg(target_arch = "x86")]
+        use std::arch::x86::*;
+        #[cfg(target_arch = "x86_64")]
+        use std::arch::x86_64::*;
+
+        use super::update_scalar;
+        use super::BASE;
+        use super::NMAX;
+
+        ///
+        /// for a block of n bytes: `lo += sum(d[i])`, `hi += n * lo + sum((n - i) * d[i])`,
+        /// `sum(d[i])` comes from `sad` against zero and `sum((n - i) * d[i])` from `maddubs` against the weights
+        ///
+        /// # Safety
+        ///
+        /// the cpu must support avx2, check with `is_x86_feature_detected!("avx2")` first
+        ///
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn update_avx2(adler: u32, bytes: &[u8]) -> u32 {
+            const BLOCK: usize = 32;
+
+            let mut lo = (adler & 0xffff) as u64;
+            let mut hi = ((adler >> 16) & 0xffff) as u64;
+
+            let (body, tail) = bytes.split_at(bytes.len() / BLOCK * BLOCK);
+
+            #[rustfmt::skip]
+            let weights = _mm256_set_epi8(
+                1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16,
+                17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32,
+            );
+            let ones = _mm256_set1_epi16(1);
+            let zero = _mm256_setzero_si256();
+
+            for group in body.chunks(NMAX / BLOCK * BLOCK) {
+                let mut v_s1 = zero;
+                let mut v_ps = zero;
+                let mut v_s2 = zero;
+
+                for block in group.chunks_exact(BLOCK) {
+                    let v = _mm256_loadu_si256(block.as_ptr() as *const __m256i);
+                    v_ps = _mm256_add_epi32(v_ps, v_s1);
+                    v_s1 = _mm256_add_epi32(v_s1, _mm256_sad_epu8(v, zero));
+                    v_s2 = _mm256_add_epi32(v_s2, _mm256_madd_epi16(_mm256_maddubs_epi16(v, weights), ones));
+                }
+
+                let mut s1 = [0u32; 8];
+                let mut ps = [0u32; 8];
+                let mut s2 = [0u32; 8];
+                _mm256_storeu_si256(s1.as_mut_ptr() as *mut __m256i, v_s1);
+                _mm256_storeu_si256(ps.as_mut_ptr() as *mut __m256i, v_ps);
+                _mm256_storeu_si256(s2.as_mut_ptr() as *mut __m256i, v_s2);
+
+                let sum = |v: [u32; 8]| v.iter().map(|n| *n as u64).sum::<u64>();
+
+                hi = (hi + lo * group.len() as u64 + sum(ps) * BLOCK as u64 + sum(s2)) % BASE as u64;
+                lo = (lo + sum(s1)) % BASE as u64;
+            }
+
+            update_scalar(((hi << 16) + lo) as u32, tail)
         }
 
+        ///
+        /// same as `update_avx2` with 16 bytes blocks
+        ///
+        /// # Safety
+        ///
+        /// the cpu must support ssse3, check with `is_x86_feature_detected!("ssse3")` first
+        ///
+        #[target_feature(enable = "ssse3")]
+        pub unsafe fn update_ssse3(adler: u32, bytes: &[u8]) -> u32 {
+            const BLOCK: usize = 16;
+
+            let mut lo = (adler & 0xffff) as u64;
+            let mut hi = ((adler >> 16) & 0xffff) as u64;
+
+            let (body, tail) = bytes.split_at(bytes.len() / BLOCK * BLOCK);
+
+            let weights = _mm_set_epi8(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16);
+            let ones = _mm_set1_epi16(1);
+            let zero = _mm_setzero_si128();
+
+            for group in body.chunks(NMAX / BLOCK * BLOCK) {
+                let mut v_s1 = zero;
+                let mut v_ps = zero;
+                let mut v_s2 = zero;
+
+                for block in group.chunks_exact(BLOCK) {
+                    let v = _mm_loadu_si128(block.as_ptr() as *const __m128i);
+                    v_ps = _mm_add_epi32(v_ps, v_s1);
+                    v_s1 = _mm_add_epi32(v_s1, _mm_sad_epu8(v, zero));
+                    v_s2 = _mm_add_epi32(v_s2, _mm_madd_epi16(_mm_maddubs_epi16(v, weights), ones));
+                }
+
+                let mut s1 = [0u32; 4];
+                let mut ps = [0u32; 4];
+                let mut s2 = [0u32; 4];
+                _mm_storeu_si128(s1.as_mut_ptr() as *mut __m128i, v_s1);
+                _mm_storeu_si128(ps.as_mut_ptr() as *mut __m128i, v_ps);
+                _mm_storeu_si128(s2.as_mut_ptr() as *mut __m128i, v_s2);
+
+                let sum = |v: [u32; 4]| v.iter().map(|n| *n as u64).sum::<u64>();
+
+                hi = (hi + lo * group.len() as u64 + sum(ps) * BLOCK as u64 + sum(s2)) % BASE as u64;
+                lo = (lo + sum(s1)) % BASE as u64;
+            }
+
+            update_scalar(((hi << 16) + lo) as u32, tail)
+        }
+    }
+
+    pub fn combine(adler_a: u32, adler_b: u32, len_b: u64) -> u32 {
+        let rem = (len_b % BASE as u64) as u32;
+
+        let mut lo = adler_a & 0xffff;
+        let mut hi = rem * lo % BASE;
+
+        lo += (adler_b & 0xffff) + BASE - 1;
+        hi += ((adler_a >> 16) & 0xffff) + ((adler_b >> 16) & 0xffff) + BASE - rem;
+
+        if BASE <= lo {
+            lo -= BASE;
+        }
+        if BASE <= lo {
+            lo -= BASE;
+        }
+        if BASE << 1 <= hi {
+            hi -= BASE << 1;
+        }
+        if BASE <= hi {
+            hi -= BASE;
+        }
+
+        (hi << 16) + lo
+    }
+
+    ///
+    /// `window_len` must be reduced modulo `BASE`
+    ///
+    pub fn roll(adler: u32, window_len: u32, out_byte: u8, in_byte: u8) -> u32 {
+        let lo = adler & 0xffff;
+        let hi = (adler >> 16) & 0xffff;
+
+        let lo = (lo + BASE - out_byte as u32 + in_byte as u32) % BASE;
+        let hi = (hi + BASE - window_len * out_byte as u32 % BASE + lo + BASE - 1) % BASE;
+
         (hi << 16) + lo
     }
 }
@@ -58,4 +417,150 @@ mod tests {
         assert_eq!(Adler32::from_str("juxt_adler32").unwrap(), Adler32::from_str("juxt_adler32").unwrap().clone());
         assert_eq!(format!("{:?}", Adler32::from_str("juxt_adler32").unwrap()), "Adler32(543425688)");
     }
+
+    #[test]
+    fn hasher_works() {
+        use std::collections::HashMap;
+        use std::hash::BuildHasher;
+
+        let mut hasher = Adler32Hasher::new();
+        Hasher::write(&mut hasher, b"juxt_");
+        Hasher::write(&mut hasher, b"adler32");
+        assert_eq!(hasher.finish(), 0x20640498);
+        assert_eq!(hasher.finalize(), Adler32::from_bytes(b"juxt_adler32"));
+
+        let build = BuildAdler32::default();
+        assert_eq!(build.hash_one("juxt_adler32"), build.hash_one("juxt_adler32"));
+
+        let mut map = HashMap::with_hasher(BuildAdler32::default());
+        map.insert("juxt", 1);
+        map.insert("adler32", 2);
+        assert_eq!(map["juxt"], 1);
+        assert_eq!(map["adler32"], 2);
+    }
+
+    fn update_naive(adler: u32, bytes: &[u8]) -> u32 {
+        let mut lo = adler & 0xffff;
+        let mut hi = (adler >> 16) & 0xffff;
+
+        for v in bytes.iter() {
+            lo = (lo + *v as u32) % imp::BASE;
+            hi = (hi + lo) % imp::BASE;
+        }
+
+        (hi << 16) + lo
+    }
+
+    #[test]
+    fn fast_works() {
+        let bytes: Vec<u8> = (0..3 * imp::NMAX as u32 + 77).map(|v| (v * 31 + v / 7) as u8).collect();
+        let full = vec![0xff; 3 * imp::NMAX + 77];
+
+        for bytes in [&bytes, &full] {
+            for len in (0..100).chain([imp::NMAX - 1, imp::NMAX, imp::NMAX + 1, 2 * imp::NMAX + 33, bytes.len()]) {
+                for adler in [1, 0xfff0fff0, 0x12345678] {
+                    let expected = update_naive(adler, &bytes[..len]);
+                    assert_eq!(imp::update(adler, &bytes[..len]), expected);
+                    assert_eq!(imp::update_scalar(adler, &bytes[..len]), expected);
+
+                    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                    {
+                        if is_x86_feature_detected!("avx2") {
+                            assert_eq!(unsafe { imp::x86::update_avx2(adler, &bytes[..len]) }, expected);
+                        }
+                        if is_x86_feature_detected!("ssse3") {
+                            assert_eq!(unsafe { imp::x86::update_ssse3(adler, &bytes[..len]) }, expected);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn combine_works() {
+        let mut seed = 0x2545f491u32;
+        let mut next = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            seed
+        };
+
+        let bytes: Vec<u8> = (0..200000).map(|_| next() as u8).collect();
+
+        for _ in 0..200 {
+            let len = next() as usize % bytes.len();
+            let mid = next() as usize % (len + 1);
+            let (x, y) = bytes[..len].split_at(mid);
+            assert_eq!(Adler32::combine(Adler32(imp::calc(x)), Adler32(imp::calc(y)), y.len() as u64), Adler32(imp::calc(&bytes[..len])));
+        }
+
+        for (x, y) in [(&b""[..], &b""[..]), (b"juxt_", b""), (b"", b"adler32"), (b"juxt_", b"adler32")] {
+            assert_eq!(Adler32::combine(Adler32::from_bytes(x), Adler32::from_bytes(y), y.len() as u64), Adler32::from_bytes(&[x, y].concat()));
+        }
+
+        let y = vec![0xff; 5 * 65521 + 7];
+        assert_eq!(Adler32::combine(Adler32::from_bytes(b"juxt"), Adler32::from_bytes(&y), y.len() as u64), Adler32::from_bytes(&[&b"juxt"[..], &y].concat()));
+    }
+
+    #[test]
+    fn incremental_works() {
+        let bytes: Vec<u8> = (0..20000u32).map(|v| (v * 31 + v / 7) as u8).collect();
+        let expected = Adler32::from_bytes(&bytes);
+
+        for chunk_size in [1, 7, 64, 5552, 5553, 20000] {
+            let mut hasher = Adler32Hasher::new();
+            bytes.chunks(chunk_size).for_each(|chunk| hasher.update(chunk));
+            assert_eq!(hasher.finalize(), expected);
+        }
+
+        let (x, y) = bytes.split_at(12345);
+        let mut hasher = Adler32Hasher::default();
+        hasher.update(x);
+        let saved: u32 = hasher.finalize().into();
+
+        let mut hasher = Adler32Hasher::resume(Adler32::from(saved));
+        std::io::copy(&mut &y[..], &mut hasher).unwrap();
+        assert_eq!(hasher.finalize(), expected);
+        assert_eq!(Adler32Hasher::from(Adler32::from_bytes(b"")), Adler32Hasher::new());
+    }
+
+    #[test]
+    fn rolling_works() {
+        let bytes: Vec<u8> = (0..70000u32).map(|v| (v * 31 + v / 7) as u8).collect();
+
+        for window_len in [1, 2, 16, 255, 4096] {
+            let mut rolling = Adler32Rolling::new(&bytes[..window_len]);
+            assert_eq!(rolling.value(), Adler32::from_bytes(&bytes[..window_len]));
+            for i in 0..300 {
+                rolling.roll(bytes[i], bytes[i + window_len]);
+                assert_eq!(rolling.value(), Adler32::from_bytes(&bytes[i + 1..i + 1 + window_len]));
+            }
+        }
+
+        let window_len = 66000;
+        let mut rolling = Adler32Rolling::new(&bytes[..window_len]);
+        for i in 0..16 {
+            rolling.roll(bytes[i], bytes[i + window_len]);
+        }
+        assert_eq!(rolling.value(), Adler32::from_bytes(&bytes[16..16 + window_len]));
+
+        let mut rolling = Adler32Rolling::new(&[0xff; 8]);
+        rolling.roll(0xff, 0x00);
+        assert_eq!(rolling.value(), Adler32::from_bytes(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00]));
+    }
+
+    #[cfg(feature = "digest")]
+    #[test]
+    fn digest_works() {
+        use digest::Digest;
+
+        let mut hasher = <Adler32Hasher as Digest>::new();
+        Digest::update(&mut hasher, b"juxt_");
+        Digest::update(&mut hasher, b"adler32");
+        assert_eq!(hasher.finalize_reset()[..], [0x20, 0x64, 0x04, 0x98]);
+        assert_eq!(Digest::finalize(hasher)[..], [0x00, 0x00, 0x00, 0x01]);
+        assert_eq!(<Adler32Hasher as Digest>::digest(b"juxt_adler32")[..], [0x20, 0x64, 0x04, 0x98]);
+    }
 }
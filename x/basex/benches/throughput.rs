@@ -0,0 +1,73 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use juxt_basex::Base64Config;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+///
+/// the per block match, as the baseline
+///
+fn naive_encode(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for block in bytes.chunks(3) {
+        let group = match block.len() {
+            3 => (block[0] as u32) << 16 | (block[1] as u32) << 8 | block[2] as u32,
+            2 => (block[0] as u32) << 16 | (block[1] as u32) << 8,
+            _ => (block[0] as u32) << 16,
+        };
+        for i in 0..4 {
+            if block.len() < i {
+                result.push('=');
+            } else {
+                result.push(ALPHABET[(group >> (18 - i * 6) & 0x3f) as usize] as char);
+            }
+        }
+    }
+    result
+}
+
+///
+/// the per block lookup, as the baseline, the text is well formed
+///
+fn naive_decode(text: &str) -> Vec<u8> {
+    let mut reverse = [0u8; 256];
+    for (i, c) in ALPHABET.iter().enumerate() {
+        reverse[*c as usize] = i as u8;
+    }
+
+    let text = text.trim_end_matches('=').as_bytes();
+    let mut result = Vec::with_capacity(text.len() / 4 * 3 + 2);
+    for block in text.chunks(4) {
+        let group = block.iter().enumerate().fold(0u32, |acc, (i, c)| acc | (reverse[*c as usize] as u32) << (18 - i * 6));
+        result.extend_from_slice(&group.to_be_bytes()[1..block.len()]);
+    }
+    result
+}
+
+fn throughput<I: AsRef<[u8]> + ?Sized, T, F: Fn(&I) -> T>(name: &str, input: &I, rounds: usize, f: F) -> T {
+    let mut result = f(black_box(input));
+
+    let now = Instant::now();
+    for _ in 0..rounds {
+        result = f(black_box(input));
+    }
+    let secs = now.elapsed().as_secs_f64();
+
+    println!("{:>8}: {:>10.2} MiB/s", name, (input.as_ref().len() * rounds) as f64 / secs / 1024.0 / 1024.0);
+
+    result
+}
+
+fn main() {
+    let bytes: Vec<u8> = (0..16 * 1024 * 1024u32).map(|v| (v * 31 + v / 7) as u8).collect();
+
+    let expected = throughput("naive", bytes.as_slice(), 4, naive_encode);
+    let encoded = throughput("encode", bytes.as_slice(), 16, |bytes| Base64Config::STANDARD.encode(bytes));
+    assert_eq!(encoded, expected);
+
+    let expected = throughput("naive", encoded.as_str(), 4, naive_decode);
+    let decoded = throughput("decode", encoded.as_str(), 16, |text| Base64Config::STANDARD.decode(text).unwrap());
+    assert_eq!(decoded, expected);
+    assert_eq!(decoded, bytes);
+}
@@ -0,0 +1,156 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::Display;
+use core::str::FromStr;
+
+use crate::DecodeError;
+
+#[derive(Debug)]
+pub struct Base16(Vec<u8>);
+
+impl From<&[u8]> for Base16 {
+    fn from(value: &[u8]) -> Self {
+        Self(imp::to_base16(value))
+    }
+}
+
+impl FromStr for Base16 {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(s.as_bytes()))
+    }
+}
+
+///
+/// uppercase, as rfc4648
+///
+impl Display for Base16 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.iter().for_each(|c| {
+            write!(f, "{}", imp::BASE16_ALPHABET[*c as usize] as char).unwrap();
+        });
+        write!(f, "")
+    }
+}
+
+impl Base16 {
+    pub fn to_string_lower(&self) -> String {
+        String::from_iter(self.0.iter().map(|c| imp::BASE16_ALPHABET_LOWER[*c as usize] as char))
+    }
+
+    ///
+    /// lenient, accept both cases
+    ///
+    pub fn decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+        imp::from_base16(text.as_bytes(), &imp::BASE16_REVERSE)
+    }
+
+    ///
+    /// strict, accept the uppercase alphabet of rfc4648 only
+    ///
+    pub fn decode_strict(text: &str) -> Result<Vec<u8>, DecodeError> {
+        imp::from_base16(text.as_bytes(), &imp::BASE16_REVERSE_STRICT)
+    }
+
+    ///
+    /// lenient, as `decode`, without the secret dependent branches and table indexing,
+    /// an invalid input falls back to `decode` for the error
+    ///
+    pub fn decode_ct(text: &str) -> Result<Vec<u8>, DecodeError> {
+        let bytes = text.as_bytes();
+        let mut result = Vec::with_capacity(bytes.len() / 2);
+        let mut invalid = 0x00;
+        for pair in bytes.chunks_exact(2) {
+            let hi = crate::imp::lookup_ct(pair[0], &imp::BASE16_ALPHABET) & crate::imp::lookup_ct(pair[0], &imp::BASE16_ALPHABET_LOWER);
+            let lo = crate::imp::lookup_ct(pair[1], &imp::BASE16_ALPHABET) & crate::imp::lookup_ct(pair[1], &imp::BASE16_ALPHABET_LOWER);
+            invalid |= (hi | lo) & 0xf0;
+            result.push((hi << 4) | lo);
+        }
+
+        if 0 != invalid || !bytes.len().is_multiple_of(2) {
+            return Self::decode(text);
+        }
+        Ok(result)
+    }
+}
+
+mod imp {
+    use alloc::vec::Vec;
+
+    use crate::reverse;
+    use crate::DecodeError;
+
+    pub const BASE16_ALPHABET: [u8; 16] = *b"0123456789ABCDEF";
+
+    pub const BASE16_ALPHABET_LOWER: [u8; 16] = *b"0123456789abcdef";
+
+    pub const BASE16_REVERSE: [u8; 256] = reverse(&BASE16_ALPHABET, 16, true);
+
+    pub const BASE16_REVERSE_STRICT: [u8; 256] = reverse(&BASE16_ALPHABET, 16, false);
+
+    pub fn to_base16(bytes: &[u8]) -> Vec<u8> {
+        let mut result = Vec::with_capacity(bytes.len() * 2);
+        for v in bytes.iter() {
+            result.push(v >> 4);
+            result.push(v & 0b00001111);
+        }
+        result
+    }
+
+    pub fn from_base16(text: &[u8], reverse: &[u8; 256]) -> Result<Vec<u8>, DecodeError> {
+        if let Some(offset) = text.iter().position(|c| 0xff == reverse[*c as usize]) {
+            return Err(DecodeError::InvalidSymbol(offset, text[offset]));
+        }
+        if !text.len().is_multiple_of(2) {
+            return Err(DecodeError::InvalidLength(text.len()));
+        }
+
+        Ok(text.chunks_exact(2).map(|pair| (reverse[pair[0] as usize] << 4) | reverse[pair[1] as usize]).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        #[rustfmt::skip]
+        let cases = [
+            ("", ""),
+            ("f", "66"),
+            ("fo", "666F"),
+            ("foo", "666F6F"),
+            ("foob", "666F6F62"),
+            ("fooba", "666F6F6261"),
+            ("foobar", "666F6F626172"),
+        ];
+        for (text, base16) in cases {
+            assert_eq!(Base16::from_str(text).unwrap().to_string(), base16);
+            assert_eq!(Base16::from_str(text).unwrap().to_string_lower(), base16.to_lowercase());
+            assert_eq!(Base16::decode_strict(base16).unwrap(), text.as_bytes());
+            assert_eq!(Base16::decode(&base16.to_lowercase()).unwrap(), text.as_bytes());
+        }
+        assert_eq!(format!("{:02x?}", Base16::from("f".as_bytes())), "Base16([06, 06])");
+
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(Base16::decode(&Base16::from(&bytes[..]).to_string_lower()).unwrap(), bytes);
+
+        assert_eq!(Base16::decode("666f6"), Err(DecodeError::InvalidLength(5)));
+        assert_eq!(Base16::decode("666g6f"), Err(DecodeError::InvalidSymbol(3, b'g')));
+        assert_eq!(Base16::decode_strict("666f6F"), Err(DecodeError::InvalidSymbol(3, b'f')));
+
+        for len in 0..bytes.len() {
+            assert_eq!(Base16::decode_ct(&Base16::from(&bytes[..len]).to_string()).unwrap(), &bytes[..len]);
+            assert_eq!(Base16::decode_ct(&Base16::from(&bytes[..len]).to_string_lower()).unwrap(), &bytes[..len]);
+        }
+        assert_eq!(Base16::decode_ct("666f6"), Err(DecodeError::InvalidLength(5)));
+        assert_eq!(Base16::decode_ct("666g6f"), Err(DecodeError::InvalidSymbol(3, b'g')));
+    }
+}
@@ -0,0 +1,423 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::Display;
+use core::str::FromStr;
+
+use crate::DecodeError;
+
+#[derive(Debug)]
+pub struct Base32(Vec<u8>);
+
+impl From<&[u8]> for Base32 {
+    fn from(value: &[u8]) -> Self {
+        Self(imp::to_base32(value))
+    }
+}
+
+impl FromStr for Base32 {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(s.as_bytes()))
+    }
+}
+
+impl Display for Base32 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.iter().for_each(|c| {
+            write!(f, "{}", imp::BASE32_ALPHABET[*c as usize] as char).unwrap();
+        });
+        write!(f, "")
+    }
+}
+
+impl Base32 {
+    pub fn to_string_unpadded(&self) -> String {
+        String::from_iter(self.0.iter().filter(|c| 32 > **c).map(|c| imp::BASE32_ALPHABET[*c as usize] as char))
+    }
+
+    ///
+    /// case insensitive, the `=` padding is optional but must be correct if present
+    ///
+    pub fn decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+        imp::from_base32(text.as_bytes(), &imp::BASE32_REVERSE)
+    }
+}
+
+///
+/// base32 with the extended hex alphabet, keeps the sort order of the encoded data
+///
+#[derive(Debug)]
+pub struct Base32Hex(Vec<u8>);
+
+impl From<&[u8]> for Base32Hex {
+    fn from(value: &[u8]) -> Self {
+        Self(imp::to_base32(value))
+    }
+}
+
+impl FromStr for Base32Hex {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(s.as_bytes()))
+    }
+}
+
+impl Display for Base32Hex {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.iter().for_each(|c| {
+            write!(f, "{}", imp::BASE32_ALPHABET_HEX[*c as usize] as char).unwrap();
+        });
+        write!(f, "")
+    }
+}
+
+impl Base32Hex {
+    pub fn to_string_unpadded(&self) -> String {
+        String::from_iter(self.0.iter().filter(|c| 32 > **c).map(|c| imp::BASE32_ALPHABET_HEX[*c as usize] as char))
+    }
+
+    ///
+    /// case insensitive, the `=` padding is optional but must be correct if present
+    ///
+    pub fn decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+        imp::from_base32(text.as_bytes(), &imp::BASE32_REVERSE_HEX)
+    }
+}
+
+///
+/// crockford base32, the bits of the bytes grouped by 5 with no padding, no ambiguous symbols,
+/// the decoding is case insensitive, maps `I`/`L` to `1` and `O` to `0`, ignores the hyphens
+///
+pub struct Base32Crockford;
+
+impl Base32Crockford {
+    pub fn encode(bytes: &[u8]) -> String {
+        String::from_iter(imp::to_crockford(bytes).iter().map(|c| imp::CROCKFORD_ALPHABET[*c as usize] as char))
+    }
+
+    ///
+    /// append the check symbol, the value of the symbols mod 37
+    ///
+    pub fn encode_check(bytes: &[u8]) -> String {
+        let values = imp::to_crockford(bytes);
+        let check = imp::check(&values);
+        String::from_iter(values.iter().chain([&check]).map(|c| imp::CROCKFORD_ALPHABET[*c as usize] as char))
+    }
+
+    pub fn decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+        let values = imp::from_crockford(text.as_bytes())?;
+        imp::to_bytes(text.as_bytes(), &values)
+    }
+
+    ///
+    /// the last symbol is the check symbol, one of the alphabet or `*~$=U`
+    ///
+    pub fn decode_check(text: &str) -> Result<Vec<u8>, DecodeError> {
+        let bytes = text.as_bytes();
+        let end = bytes.iter().rposition(|c| b'-' != *c).ok_or(DecodeError::InvalidLength(bytes.len()))?;
+
+        let check = imp::CROCKFORD_REVERSE[bytes[end] as usize];
+        if 0xff == check {
+            return Err(DecodeError::InvalidSymbol(end, bytes[end]));
+        }
+
+        let values = imp::from_crockford(&bytes[..end])?;
+        if check != imp::check(&values) {
+            return Err(DecodeError::InvalidChecksum(bytes.len()));
+        }
+        imp::to_bytes(&bytes[..end], &values)
+    }
+
+    ///
+    /// 26 symbols, the first one carries the top 3 bits only
+    ///
+    pub fn encode_ulid(ulid: u128) -> String {
+        String::from_iter((0..26).rev().map(|i| imp::CROCKFORD_ALPHABET[(ulid >> (i * 5)) as usize & 0b00011111] as char))
+    }
+
+    ///
+    /// exactly 26 symbols, no hyphens, the first one must be `0`..=`7`
+    ///
+    pub fn decode_ulid(text: &str) -> Result<u128, DecodeError> {
+        let bytes = text.as_bytes();
+        if 26 != bytes.len() {
+            return Err(DecodeError::InvalidLength(bytes.len()));
+        }
+
+        let mut ulid = 0u128;
+        for (offset, c) in bytes.iter().enumerate() {
+            let v = imp::CROCKFORD_REVERSE[*c as usize];
+            if 32 <= v || (0 == offset && 7 < v) {
+                return Err(DecodeError::InvalidSymbol(offset, *c));
+            }
+            ulid = (ulid << 5) | v as u128;
+        }
+        Ok(ulid)
+    }
+}
+
+mod imp {
+    use alloc::vec::Vec;
+
+    use crate::reverse;
+    use crate::DecodeError;
+
+    #[rustfmt::skip]
+    pub const BASE32_ALPHABET: [u8; 33] = [
+        b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', b'P',
+        b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z', b'2', b'3', b'4', b'5', b'6', b'7',
+        b'=',
+    ];
+
+    #[rustfmt::skip]
+    pub const BASE32_ALPHABET_HEX: [u8; 33] = [
+        b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', b'A', b'B', b'C', b'D', b'E', b'F',
+        b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', b'P', b'Q', b'R', b'S', b'T', b'U', b'V',
+        b'=',
+    ];
+
+    pub const BASE32_REVERSE: [u8; 256] = reverse(&BASE32_ALPHABET, 32, true);
+
+    pub const BASE32_REVERSE_HEX: [u8; 256] = reverse(&BASE32_ALPHABET_HEX, 32, true);
+
+    pub const CROCKFORD_ALPHABET: [u8; 37] = *b"0123456789ABCDEFGHJKMNPQRSTVWXYZ*~$=U";
+
+    pub const CROCKFORD_REVERSE: [u8; 256] = crockford_reverse(&CROCKFORD_ALPHABET);
+
+    ///
+    /// both cases, and `I`/`L` -> 1, `O` -> 0
+    ///
+    const fn crockford_reverse(alphabet: &[u8; 37]) -> [u8; 256] {
+        let mut table = reverse(alphabet, 37, true);
+        table[b'I' as usize] = 1;
+        table[b'i' as usize] = 1;
+        table[b'L' as usize] = 1;
+        table[b'l' as usize] = 1;
+        table[b'O' as usize] = 0;
+        table[b'o' as usize] = 0;
+        table
+    }
+
+    ///
+    /// the count of symbols carrying data for a block of n bytes
+    ///
+    const SYMBOLS: [usize; 6] = [0, 2, 4, 5, 7, 8];
+
+    pub fn to_base32(bytes: &[u8]) -> Vec<u8> {
+        let mut result = Vec::with_capacity(bytes.len().div_ceil(5) * 8);
+        for block in bytes.chunks(5) {
+            let mut n = 0u64;
+            for i in 0..5 {
+                n = (n << 8) | *block.get(i).unwrap_or(&0) as u64;
+            }
+            for i in 0..8 {
+                if SYMBOLS[block.len()] > i {
+                    result.push((n >> (35 - i * 5)) as u8 & 0b00011111);
+                } else {
+                    result.push(32);
+                }
+            }
+        }
+        result
+    }
+
+    pub fn to_crockford(bytes: &[u8]) -> Vec<u8> {
+        let mut result = to_base32(bytes);
+        result.truncate(bytes.len() / 5 * 8 + SYMBOLS[bytes.len() % 5]);
+        result
+    }
+
+    ///
+    /// the values of the symbols, the hyphens skipped, the check symbols rejected
+    ///
+    pub fn from_crockford(text: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let mut result = Vec::with_capacity(text.len());
+        for (offset, c) in text.iter().enumerate() {
+            match CROCKFORD_REVERSE[*c as usize] {
+                _ if b'-' == *c => continue,
+                v if 32 > v => result.push(v),
+                _ => return Err(DecodeError::InvalidSymbol(offset, *c)),
+            }
+        }
+        Ok(result)
+    }
+
+    pub fn check(values: &[u8]) -> u8 {
+        values.iter().fold(0u32, |acc, v| (acc * 32 + *v as u32) % 37) as u8
+    }
+
+    ///
+    /// the unused bits must be less than 5 and all zeros, `text` is for the offsets of the errors
+    ///
+    pub fn to_bytes(text: &[u8], values: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let bits = values.len() * 5 % 8;
+        if 5 <= bits {
+            return Err(DecodeError::InvalidLength(text.len()));
+        }
+        if values.last().is_some_and(|v| 0 != v & ((1 << bits) - 1)) {
+            let offset = text.iter().rposition(|c| b'-' != *c).unwrap_or(0);
+            return Err(DecodeError::TrailingBits(offset));
+        }
+
+        let mut result = Vec::with_capacity(values.len() * 5 / 8);
+        let mut acc = 0u32;
+        let mut acc_bits = 0;
+        for v in values {
+            acc = ((acc << 5) | *v as u32) & 0xfff;
+            acc_bits += 5;
+            if 8 <= acc_bits {
+                acc_bits -= 8;
+                result.push((acc >> acc_bits) as u8);
+            }
+        }
+        Ok(result)
+    }
+
+    pub fn from_base32(text: &[u8], reverse: &[u8; 256]) -> Result<Vec<u8>, DecodeError> {
+        let mut end = text.len();
+        while 0 < end && b'=' == text[end - 1] {
+            end -= 1;
+        }
+
+        if let Some(offset) = text[..end].iter().position(|c| 0xff == reverse[*c as usize]) {
+            let c = text[offset];
+            return Err(if b'=' == c { DecodeError::InvalidPadding(offset) } else { DecodeError::InvalidSymbol(offset, c) });
+        }
+
+        let bytes_count = match SYMBOLS.iter().position(|v| *v == end % 8) {
+            Some(bytes_count) => bytes_count,
+            None => return Err(DecodeError::InvalidLength(end)),
+        };
+
+        let pads = text.len() - end;
+        if 0 < pads && (!text.len().is_multiple_of(8) || end.is_multiple_of(8)) {
+            return Err(DecodeError::InvalidPadding(end));
+        }
+
+        let mut result = Vec::with_capacity(end / 8 * 5 + bytes_count);
+
+        for (i, block) in text[..end].chunks(8).enumerate() {
+            let mut n = 0u64;
+            for j in 0..8 {
+                n = (n << 5) | block.get(j).map_or(0, |c| reverse[*c as usize]) as u64;
+            }
+
+            let bytes_count = if 8 == block.len() { 5 } else { bytes_count };
+            if 0 != n & u64::MAX.checked_shr(24 + bytes_count as u32 * 8).unwrap_or(0) {
+                return Err(DecodeError::TrailingBits(i * 8 + block.len() - 1));
+            }
+
+            for j in 0..bytes_count {
+                result.push((n >> (32 - j * 8)) as u8);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        #[rustfmt::skip]
+        let cases = [
+            ("", "", ""),
+            ("f", "MY======", "CO======"),
+            ("fo", "MZXQ====", "CPNG===="),
+            ("foo", "MZXW6===", "CPNMU==="),
+            ("foob", "MZXW6YQ=", "CPNMUOG="),
+            ("fooba", "MZXW6YTB", "CPNMUOJ1"),
+            ("foobar", "MZXW6YTBOI======", "CPNMUOJ1E8======"),
+        ];
+        for (text, base32, base32hex) in cases {
+            assert_eq!(Base32::from_str(text).unwrap().to_string(), base32);
+            assert_eq!(Base32Hex::from_str(text).unwrap().to_string(), base32hex);
+            assert_eq!(Base32::from_str(text).unwrap().to_string_unpadded(), base32.trim_end_matches('='));
+            assert_eq!(Base32Hex::from_str(text).unwrap().to_string_unpadded(), base32hex.trim_end_matches('='));
+            assert_eq!(Base32::decode(base32).unwrap(), text.as_bytes());
+            assert_eq!(Base32Hex::decode(base32hex).unwrap(), text.as_bytes());
+            assert_eq!(Base32::decode(&base32.to_lowercase()).unwrap(), text.as_bytes());
+            assert_eq!(Base32Hex::decode(base32hex.trim_end_matches('=')).unwrap(), text.as_bytes());
+        }
+        assert_eq!(format!("{:02x?}", Base32::from("f".as_bytes())), "Base32([0c, 18, 20, 20, 20, 20, 20, 20])");
+    }
+
+    #[test]
+    fn crockford_works() {
+        #[rustfmt::skip]
+        let cases = [
+            ("", "", "0"),
+            ("f", "CR", "CR1"),
+            ("fo", "CSQG", "CSQGS"),
+            ("foobar", "CSQPYRK1E8", "CSQPYRK1E8R"),
+            ("Hello World!", "91JPRV3F41BPYWKCCGGG", "91JPRV3F41BPYWKCCGGG~"),
+        ];
+        for (text, crockford, check) in cases {
+            assert_eq!(Base32Crockford::encode(text.as_bytes()), crockford);
+            assert_eq!(Base32Crockford::encode_check(text.as_bytes()), check);
+            assert_eq!(Base32Crockford::decode(crockford).unwrap(), text.as_bytes());
+            assert_eq!(Base32Crockford::decode(&crockford.to_lowercase()).unwrap(), text.as_bytes());
+            assert_eq!(Base32Crockford::decode_check(check).unwrap(), text.as_bytes());
+        }
+
+        let bytes: Vec<u8> = (0..=255).collect();
+        for len in 0..bytes.len() {
+            assert_eq!(Base32Crockford::decode(&Base32Crockford::encode(&bytes[..len])).unwrap(), &bytes[..len]);
+            assert_eq!(Base32Crockford::decode_check(&Base32Crockford::encode_check(&bytes[..len])).unwrap(), &bytes[..len]);
+        }
+
+        assert_eq!(Base32Crockford::decode("91JPRV3F-41BPYWKC-CGGG").unwrap(), b"Hello World!");
+        assert_eq!(Base32Crockford::decode("9IJPRV3F4lBPYWKCCGGG").unwrap(), b"Hello World!");
+        assert_eq!(Base32Crockford::decode("CSQPYRKIE8").unwrap(), b"foobar");
+        assert_eq!(Base32Crockford::decode("ZZZZZZZZ").unwrap(), [0xff; 5]);
+        assert_eq!(Base32Crockford::decode_check("ZZZZ-ZZZZ-F").unwrap(), [0xff; 5]);
+        assert_eq!(Base32Crockford::decode_check("ZZZZ-ZZZZ-f-").unwrap(), [0xff; 5]);
+        assert_eq!(Base32Crockford::decode("O0").unwrap(), [0x00]);
+
+        assert_eq!(Base32Crockford::decode("CSU"), Err(DecodeError::InvalidSymbol(2, b'U')));
+        assert_eq!(Base32Crockford::decode("CSQ"), Err(DecodeError::InvalidLength(3)));
+        assert_eq!(Base32Crockford::decode("CS-"), Err(DecodeError::TrailingBits(1)));
+        assert_eq!(Base32Crockford::decode_check("CSQGT"), Err(DecodeError::InvalidChecksum(5)));
+        assert_eq!(Base32Crockford::decode_check("CSQG!"), Err(DecodeError::InvalidSymbol(4, b'!')));
+        assert_eq!(Base32Crockford::decode_check("-"), Err(DecodeError::InvalidLength(1)));
+
+        let ulid = 0x01563e3ab5d3d6764c61efb99302bd5b;
+        assert_eq!(Base32Crockford::encode_ulid(ulid), "01ARZ3NDEKTSV4RRFFQ69G5FAV");
+        assert_eq!(Base32Crockford::decode_ulid("01ARZ3NDEKTSV4RRFFQ69G5FAV"), Ok(ulid));
+        assert_eq!(Base32Crockford::decode_ulid("01arz3ndektsv4rrffq69g5fav"), Ok(ulid));
+        assert_eq!(Base32Crockford::encode_ulid(u128::MAX), "7ZZZZZZZZZZZZZZZZZZZZZZZZZ");
+        assert_eq!(Base32Crockford::decode_ulid("7ZZZZZZZZZZZZZZZZZZZZZZZZZ"), Ok(u128::MAX));
+        assert_eq!(Base32Crockford::decode_ulid("80000000000000000000000000"), Err(DecodeError::InvalidSymbol(0, b'8')));
+        assert_eq!(Base32Crockford::decode_ulid("01ARZ3NDEKTSV4RRFFQ69G5FA"), Err(DecodeError::InvalidLength(25)));
+        assert_eq!(Base32Crockford::decode_ulid("01ARZ3NDEKTSV4RRFFQ69G5FA*"), Err(DecodeError::InvalidSymbol(25, b'*')));
+    }
+
+    #[test]
+    fn decode_works() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        for len in 0..bytes.len() {
+            assert_eq!(Base32::decode(&Base32::from(&bytes[..len]).to_string()).unwrap(), &bytes[..len]);
+            assert_eq!(Base32Hex::decode(&Base32Hex::from(&bytes[..len]).to_string_unpadded()).unwrap(), &bytes[..len]);
+        }
+
+        assert_eq!(Base32::decode("MZXW1YQ="), Err(DecodeError::InvalidSymbol(4, b'1')));
+        assert_eq!(Base32Hex::decode("CPNMUOW="), Err(DecodeError::InvalidSymbol(6, b'W')));
+        assert_eq!(Base32::decode("MZX"), Err(DecodeError::InvalidLength(3)));
+        assert_eq!(Base32::decode("MZXW6YQ"), Ok(b"foob".to_vec()));
+        assert_eq!(Base32::decode("MZXW6YQ=="), Err(DecodeError::InvalidPadding(7)));
+        assert_eq!(Base32::decode("MZXQ===="), Ok(b"fo".to_vec()));
+        assert_eq!(Base32::decode("MZ=Q===="), Err(DecodeError::InvalidPadding(2)));
+        assert_eq!(Base32::decode("MZ======"), Err(DecodeError::TrailingBits(1)));
+        assert_eq!(Base32::decode("MZXW6YR="), Err(DecodeError::TrailingBits(6)));
+    }
+}
@@ -0,0 +1,221 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::radix;
+use crate::reverse;
+use crate::DecodeError;
+
+///
+/// base58 with a selectable alphabet, every leading zero byte maps to one leading zero symbol and back
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Base58 {
+    alphabet: [u8; 58],
+    reverse: [u8; 256],
+}
+
+impl Base58 {
+    pub const BITCOIN: Self = Self::from_alphabet(b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz");
+
+    pub const RIPPLE: Self = Self::from_alphabet(b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz");
+
+    pub const FLICKR: Self = Self::from_alphabet(b"123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ");
+
+    const fn from_alphabet(alphabet: &[u8; 58]) -> Self {
+        Self {
+            alphabet: *alphabet,
+            reverse: reverse(alphabet, 58, false),
+        }
+    }
+
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        String::from_iter(radix::imp::to_radix(bytes, 58).iter().map(|c| self.alphabet[*c as usize] as char))
+    }
+
+    pub fn decode(&self, text: &str) -> Result<Vec<u8>, DecodeError> {
+        imp::from_base58(text.as_bytes(), &self.reverse)
+    }
+}
+
+///
+/// base58 of the payload followed by the first 4 bytes of its double sha256,
+/// the version byte, if any, is a part of the payload
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Base58Check(Base58);
+
+impl Base58Check {
+    pub const BITCOIN: Self = Self(Base58::BITCOIN);
+
+    pub const RIPPLE: Self = Self(Base58::RIPPLE);
+
+    pub const fn new(base58: Base58) -> Self {
+        Self(base58)
+    }
+
+    pub fn encode(&self, payload: &[u8]) -> String {
+        let mut bytes = Vec::with_capacity(payload.len() + 4);
+        bytes.extend_from_slice(payload);
+        bytes.extend_from_slice(&imp::checksum(payload));
+        self.0.encode(&bytes)
+    }
+
+    ///
+    /// return the payload with the checksum stripped
+    ///
+    pub fn decode(&self, text: &str) -> Result<Vec<u8>, DecodeError> {
+        let mut bytes = self.0.decode(text)?;
+        if 4 > bytes.len() {
+            return Err(DecodeError::InvalidLength(text.len()));
+        }
+
+        let payload_len = bytes.len() - 4;
+        if imp::checksum(&bytes[..payload_len]) != bytes[payload_len..] {
+            return Err(DecodeError::InvalidChecksum(text.len()));
+        }
+
+        bytes.truncate(payload_len);
+        Ok(bytes)
+    }
+}
+
+mod imp {
+    use alloc::vec::Vec;
+
+    use crate::radix;
+    use crate::DecodeError;
+
+    pub fn from_base58(text: &[u8], reverse: &[u8; 256]) -> Result<Vec<u8>, DecodeError> {
+        if let Some(offset) = text.iter().position(|c| 0xff == reverse[*c as usize]) {
+            return Err(DecodeError::InvalidSymbol(offset, text[offset]));
+        }
+
+        let digits: Vec<u8> = text.iter().map(|c| reverse[*c as usize]).collect();
+        Ok(radix::imp::from_radix(&digits, 58))
+    }
+
+    pub fn checksum(payload: &[u8]) -> [u8; 4] {
+        let hash = sha256(&sha256(payload));
+        [hash[0], hash[1], hash[2], hash[3]]
+    }
+
+    #[rustfmt::skip]
+    const K: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+        0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+        0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+        0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+        0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+        0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+        0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+        0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+    ];
+
+    ///
+    /// fips 180-4, just enough for the checksum
+    ///
+    pub fn sha256(bytes: &[u8]) -> [u8; 32] {
+        let mut state: [u32; 8] = [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19];
+
+        let mut message = bytes.to_vec();
+        message.push(0x80);
+        while 56 != message.len() % 64 {
+            message.push(0x00);
+        }
+        message.extend_from_slice(&(bytes.len() as u64 * 8).to_be_bytes());
+
+        for block in message.chunks_exact(64) {
+            let mut w = [0u32; 64];
+            for i in 0..16 {
+                w[i] = u32::from_be_bytes([block[i * 4], block[i * 4 + 1], block[i * 4 + 2], block[i * 4 + 3]]);
+            }
+            for i in 16..64 {
+                let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+                let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+                w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+            }
+
+            let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+            for i in 0..64 {
+                let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+                let ch = (e & f) ^ (!e & g);
+                let t1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(K[i]).wrapping_add(w[i]);
+                let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+                let maj = (a & b) ^ (a & c) ^ (b & c);
+                let t2 = s0.wrapping_add(maj);
+
+                h = g;
+                g = f;
+                f = e;
+                e = d.wrapping_add(t1);
+                d = c;
+                c = b;
+                b = a;
+                a = t1.wrapping_add(t2);
+            }
+
+            for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+                *s = s.wrapping_add(v);
+            }
+        }
+
+        let mut result = [0x00; 32];
+        for (chunk, s) in result.chunks_exact_mut(4).zip(state) {
+            chunk.copy_from_slice(&s.to_be_bytes());
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        #[rustfmt::skip]
+        let cases: [(&[u8], &str, &str, &str); 4] = [
+            (b"", "", "", ""),
+            (b"\x00", "1", "r", "1"),
+            (b"\x00\x00\x28\x7f\xb4\xcd", "11233QC4", "rrpssQUh", "11233pc4"),
+            (b"Hello World!", "2NEpo7TZRRrLZSi2U", "p4NFofTZRRiLZS5p7", "2nePN7syqqRkyrH2t"),
+        ];
+        for (bytes, bitcoin, ripple, flickr) in cases {
+            assert_eq!(Base58::BITCOIN.encode(bytes), bitcoin);
+            assert_eq!(Base58::RIPPLE.encode(bytes), ripple);
+            assert_eq!(Base58::FLICKR.encode(bytes), flickr);
+            assert_eq!(Base58::BITCOIN.decode(bitcoin).unwrap(), bytes);
+            assert_eq!(Base58::RIPPLE.decode(ripple).unwrap(), bytes);
+            assert_eq!(Base58::FLICKR.decode(flickr).unwrap(), bytes);
+        }
+
+        let bytes: Vec<u8> = (0..=255).rev().collect();
+        for len in 0..bytes.len() {
+            assert_eq!(Base58::BITCOIN.decode(&Base58::BITCOIN.encode(&bytes[..len])).unwrap(), &bytes[..len]);
+        }
+        let zeros = [0x00; 10];
+        for len in 0..zeros.len() {
+            assert_eq!(Base58::BITCOIN.encode(&zeros[..len]), "1".repeat(len));
+            assert_eq!(Base58::BITCOIN.decode(&"1".repeat(len)).unwrap(), &zeros[..len]);
+        }
+
+        assert_eq!(Base58::BITCOIN.decode("2NEpo0"), Err(DecodeError::InvalidSymbol(5, b'0')));
+        assert_eq!(Base58::BITCOIN.decode("2NEpl"), Err(DecodeError::InvalidSymbol(4, b'l')));
+    }
+
+    #[test]
+    fn check_works() {
+        assert_eq!(imp::sha256(b"abc")[..4], [0xba, 0x78, 0x16, 0xbf]);
+        assert_eq!(imp::sha256(&[b'a'; 64])[..4], [0xff, 0xe0, 0x54, 0xfe]);
+
+        let payload = [0x00, 0x01, 0x09, 0x66, 0x77, 0x60, 0x06, 0x95, 0x3d, 0x55, 0x67, 0x43, 0x9e, 0x5e, 0x39, 0xf8, 0x6a, 0x0d, 0x27, 0x3b, 0xee];
+        assert_eq!(Base58Check::BITCOIN.encode(&payload), "16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM");
+        assert_eq!(Base58Check::BITCOIN.decode("16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM").unwrap(), payload);
+        assert_eq!(Base58Check::RIPPLE.encode(&[0x00; 21]), "rrrrrrrrrrrrrrrrrrrrrhoLvTp");
+        assert_eq!(Base58Check::new(Base58::BITCOIN).encode(b""), "3QJmnh");
+        assert_eq!(Base58Check::BITCOIN.decode("3QJmnh").unwrap(), b"");
+
+        assert_eq!(Base58Check::BITCOIN.decode("16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvN"), Err(DecodeError::InvalidChecksum(33)));
+        assert_eq!(Base58Check::BITCOIN.decode("2NE"), Err(DecodeError::InvalidLength(3)));
+    }
+}
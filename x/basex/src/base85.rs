@@ -0,0 +1,249 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::Display;
+
+use crate::DecodeError;
+
+///
+/// ascii85 of adobe, `<~` and `~>` around the symbols `!`..=`u`, `z` for a group of 4 zero bytes
+///
+pub struct Ascii85;
+
+impl Ascii85 {
+    pub fn encode(bytes: &[u8]) -> String {
+        let mut result = String::with_capacity(bytes.len().div_ceil(4) * 5 + 4);
+        result.push_str("<~");
+        for block in bytes.chunks(4) {
+            let group = imp::to_group(block);
+            if 4 == block.len() && 0 == group {
+                result.push('z');
+                continue;
+            }
+            imp::to_base85(group)[..block.len() + 1].iter().for_each(|d| result.push((b'!' + d) as char));
+        }
+        result.push_str("~>");
+        result
+    }
+
+    ///
+    /// the delimiters are optional, the whitespace is ignored, the offset is counted from the start of the text
+    ///
+    pub fn decode(text: &str) -> Result<Vec<u8>, Base85Error> {
+        let text = text.as_bytes();
+        let mut begin = text.iter().position(|c| !c.is_ascii_whitespace()).unwrap_or(text.len());
+        let mut end = text.iter().rposition(|c| !c.is_ascii_whitespace()).map_or(begin, |i| i + 1);
+
+        if text[begin..end].starts_with(b"<~") {
+            if !text[begin + 2..end].ends_with(b"~>") {
+                return Err(Base85Error::Decode(DecodeError::InvalidLength(end)));
+            }
+            begin += 2;
+            end -= 2;
+        }
+
+        let mut result = Vec::with_capacity((end - begin) / 5 * 4 + 4);
+        let mut digits = [0u8; 5];
+        let mut digits_len = 0;
+        let mut group_offset = begin;
+
+        for (offset, c) in text.iter().enumerate().take(end).skip(begin) {
+            match c {
+                c if c.is_ascii_whitespace() => continue,
+                b'z' if 0 == digits_len => result.extend_from_slice(&[0x00; 4]),
+                b'!'..=b'u' => {
+                    if 0 == digits_len {
+                        group_offset = offset;
+                    }
+                    digits[digits_len] = c - b'!';
+                    digits_len += 1;
+                    if 5 == digits_len {
+                        let group = imp::from_base85(&digits).ok_or(Base85Error::InvalidGroup(group_offset))?;
+                        result.extend_from_slice(&group.to_be_bytes());
+                        digits_len = 0;
+                    }
+                }
+                _ => return Err(Base85Error::Decode(DecodeError::InvalidSymbol(offset, *c))),
+            }
+        }
+
+        match digits_len {
+            0 => {}
+            1 => return Err(Base85Error::Decode(DecodeError::InvalidLength(end))),
+            _ => {
+                digits[digits_len..].fill(84);
+                let group = imp::from_base85(&digits).ok_or(Base85Error::InvalidGroup(group_offset))?;
+                result.extend_from_slice(&group.to_be_bytes()[..digits_len - 1]);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+///
+/// z85 of zeromq, the length of the bytes must be a multiple of 4, no delimiters, no shortcut
+///
+pub struct Z85;
+
+impl Z85 {
+    ///
+    /// `Base85Error::UnalignedLength` if the length of `bytes` is not a multiple of 4, the padding is left to the caller
+    ///
+    pub fn encode(bytes: &[u8]) -> Result<String, Base85Error> {
+        if !bytes.len().is_multiple_of(4) {
+            return Err(Base85Error::UnalignedLength(bytes.len()));
+        }
+
+        let mut result = String::with_capacity(bytes.len() / 4 * 5);
+        for block in bytes.chunks_exact(4) {
+            imp::to_base85(imp::to_group(block)).iter().for_each(|d| result.push(imp::Z85_ALPHABET[*d as usize] as char));
+        }
+        Ok(result)
+    }
+
+    pub fn decode(text: &str) -> Result<Vec<u8>, Base85Error> {
+        let text = text.as_bytes();
+        if let Some(offset) = text.iter().position(|c| 0xff == imp::Z85_REVERSE[*c as usize]) {
+            return Err(Base85Error::Decode(DecodeError::InvalidSymbol(offset, text[offset])));
+        }
+        if !text.len().is_multiple_of(5) {
+            return Err(Base85Error::Decode(DecodeError::InvalidLength(text.len())));
+        }
+
+        let mut result = Vec::with_capacity(text.len() / 5 * 4);
+        for (i, block) in text.chunks_exact(5).enumerate() {
+            let digits = [0, 1, 2, 3, 4].map(|j| imp::Z85_REVERSE[block[j] as usize]);
+            let group = imp::from_base85(&digits).ok_or(Base85Error::InvalidGroup(i * 5))?;
+            result.extend_from_slice(&group.to_be_bytes());
+        }
+        Ok(result)
+    }
+}
+
+///
+/// the errors of ascii85 and z85, the offset is the index in the text, as `DecodeError`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Base85Error {
+    /// the length of the bytes to encode is not a multiple of 4, z85 only
+    UnalignedLength(usize),
+    /// the group of 5 symbols at the offset overflows 32 bits
+    InvalidGroup(usize),
+    Decode(DecodeError),
+}
+
+impl Display for Base85Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Base85Error::UnalignedLength(len) => write!(f, "length {} is not a multiple of 4", len),
+            Base85Error::InvalidGroup(offset) => write!(f, "group overflows 32 bits at offset {}", offset),
+            Base85Error::Decode(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for Base85Error {}
+
+mod imp {
+    use crate::reverse;
+
+    pub const Z85_ALPHABET: [u8; 85] = *b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+    pub const Z85_REVERSE: [u8; 256] = reverse(&Z85_ALPHABET, 85, false);
+
+    ///
+    /// big endian, the missing bytes of the last block are zeros
+    ///
+    pub fn to_group(block: &[u8]) -> u32 {
+        let mut group = [0x00; 4];
+        group[..block.len()].copy_from_slice(block);
+        u32::from_be_bytes(group)
+    }
+
+    ///
+    /// 5 digits of radix 85, the most significant first
+    ///
+    pub fn to_base85(mut group: u32) -> [u8; 5] {
+        let mut digits = [0u8; 5];
+        for d in digits.iter_mut().rev() {
+            *d = (group % 85) as u8;
+            group /= 85;
+        }
+        digits
+    }
+
+    ///
+    /// `None` if the value overflows 32 bits
+    ///
+    pub fn from_base85(digits: &[u8; 5]) -> Option<u32> {
+        let group = digits.iter().fold(0u64, |acc, d| acc * 85 + *d as u64);
+        u32::try_from(group).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        #[rustfmt::skip]
+        let cases: [(&[u8], &str); 11] = [
+            (b"", "<~~>"),
+            (b"h", "<~BE~>"),
+            (b"he", "<~BOq~>"),
+            (b"hel", "<~BOtu~>"),
+            (b"hell", "<~BOu!r~>"),
+            (b"hello", "<~BOu!rDZ~>"),
+            (b"Hello World!", "<~87cURD]i,\"Ebo80~>"),
+            (b"\x00", "<~!!~>"),
+            (b"\x00\x00\x00\x00", "<~z~>"),
+            (b"\x00\x00\x00\x00\x00", "<~z!!~>"),
+            (b"\xff\xff\xff\xff", "<~s8W-!~>"),
+        ];
+        for (bytes, ascii85) in cases {
+            assert_eq!(Ascii85::encode(bytes), ascii85);
+            assert_eq!(Ascii85::decode(ascii85).unwrap(), bytes);
+            assert_eq!(Ascii85::decode(&ascii85[2..ascii85.len() - 2]).unwrap(), bytes);
+        }
+
+        let bytes: Vec<u8> = (0..=255).collect();
+        for len in 0..bytes.len() {
+            assert_eq!(Ascii85::decode(&Ascii85::encode(&bytes[..len])).unwrap(), &bytes[..len]);
+        }
+
+        assert_eq!(Ascii85::decode(" <~87cUR\n  DZ~>\n").unwrap(), b"Hello");
+        assert_eq!(Ascii85::decode("<~87cURDZ"), Err(Base85Error::Decode(DecodeError::InvalidLength(9))));
+        assert_eq!(Ascii85::decode("<~87cURD~>"), Err(Base85Error::Decode(DecodeError::InvalidLength(8))));
+        assert_eq!(Ascii85::decode("<~87zURDZ~>"), Err(Base85Error::Decode(DecodeError::InvalidSymbol(4, b'z'))));
+        assert_eq!(Ascii85::decode("<~87cURDv~>"), Err(Base85Error::Decode(DecodeError::InvalidSymbol(8, b'v'))));
+        assert_eq!(Ascii85::decode("<~z s8W-\"~>"), Err(Base85Error::InvalidGroup(4)));
+        assert_eq!(Ascii85::decode("<~uu~>"), Err(Base85Error::InvalidGroup(2)));
+    }
+
+    #[test]
+    fn z85_works() {
+        let bytes = [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b];
+        assert_eq!(Z85::encode(&bytes).unwrap(), "HelloWorld");
+        assert_eq!(Z85::decode("HelloWorld").unwrap(), bytes);
+        assert_eq!(Z85::encode(b"").unwrap(), "");
+        assert_eq!(Z85::encode(&[0xff; 4]).unwrap(), "%nSc0");
+
+        let bytes: Vec<u8> = (0..=255).collect();
+        for len in (0..bytes.len()).step_by(4) {
+            assert_eq!(Z85::decode(&Z85::encode(&bytes[..len]).unwrap()).unwrap(), &bytes[..len]);
+        }
+
+        assert_eq!(Z85::decode("Hello"), Ok([0x86, 0x4f, 0xd2, 0x6f].to_vec()));
+        assert_eq!(Z85::decode("HelloWorl"), Err(Base85Error::Decode(DecodeError::InvalidLength(9))));
+        assert_eq!(Z85::decode("Hello World"), Err(Base85Error::Decode(DecodeError::InvalidSymbol(5, b' '))));
+        assert_eq!(Z85::decode("Hello%nSc1"), Err(Base85Error::InvalidGroup(5)));
+
+        assert_eq!(Z85::encode(b"juxt_basex"), Err(Base85Error::UnalignedLength(10)));
+        assert_eq!(Base85Error::UnalignedLength(10).to_string(), "length 10 is not a multiple of 4");
+    }
+}
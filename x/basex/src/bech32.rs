@@ -0,0 +1,278 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::Display;
+
+use crate::DecodeError;
+
+///
+/// bech32 of bip-173 and bech32m of bip-350, `hrp` `1` `data` `checksum`,
+/// they differ in the constant of the checksum only, there is no limit on the length, as age
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bech32 {
+    constant: u32,
+}
+
+impl Bech32 {
+    pub const BECH32: Self = Self { constant: 1 };
+
+    pub const BECH32M: Self = Self { constant: 0x2bc830a3 };
+
+    ///
+    /// regroup the bytes into 5 bit values, the hrp is lowercased
+    ///
+    pub fn encode(&self, hrp: &str, bytes: &[u8]) -> Result<String, Bech32Error> {
+        self.encode_5bit(hrp, &imp::regroup(bytes, 8, 5))
+    }
+
+    ///
+    /// return the lowercase hrp and the bytes, the bits left over by the regrouping must be less than 5 and all zeros
+    ///
+    pub fn decode(&self, text: &str) -> Result<(String, Vec<u8>), Bech32Error> {
+        let (hrp, values) = self.decode_5bit(text)?;
+
+        let data_end = text.len() - 6;
+        if 5 <= values.len() * 5 % 8 {
+            return Err(Bech32Error::Decode(DecodeError::InvalidLength(data_end)));
+        }
+        let bytes = imp::regroup(&values, 5, 8);
+        if values.last().is_some_and(|v| 0 != v & ((1 << (values.len() * 5 % 8)) - 1)) {
+            return Err(Bech32Error::Decode(DecodeError::TrailingBits(data_end - 1)));
+        }
+
+        Ok((hrp, bytes[..values.len() * 5 / 8].to_vec()))
+    }
+
+    ///
+    /// the values must be less than 32, as the witness version and program of segwit,
+    /// `Bech32Error::InvalidValue` with the index of the first one that is not
+    ///
+    pub fn encode_5bit(&self, hrp: &str, values: &[u8]) -> Result<String, Bech32Error> {
+        let hrp = hrp.to_ascii_lowercase();
+        if hrp.is_empty() {
+            return Err(Bech32Error::InvalidHrp(0));
+        }
+        if let Some(offset) = hrp.bytes().position(|c| !(33..=126).contains(&c)) {
+            return Err(Bech32Error::InvalidHrp(offset));
+        }
+        if let Some(index) = values.iter().position(|v| 32 <= *v) {
+            return Err(Bech32Error::InvalidValue(index));
+        }
+
+        let checksum = imp::checksum(hrp.as_bytes(), values, self.constant);
+
+        let mut result = String::with_capacity(hrp.len() + 1 + values.len() + 6);
+        result.push_str(&hrp);
+        result.push('1');
+        values.iter().chain(&checksum).for_each(|v| result.push(imp::BECH32_ALPHABET[*v as usize] as char));
+        Ok(result)
+    }
+
+    ///
+    /// return the lowercase hrp and the 5 bit values, the checksum stripped
+    ///
+    pub fn decode_5bit(&self, text: &str) -> Result<(String, Vec<u8>), Bech32Error> {
+        let bytes = text.as_bytes();
+        if let Some(offset) = bytes.iter().position(|c| !(33..=126).contains(c)) {
+            return Err(Bech32Error::Decode(DecodeError::InvalidSymbol(offset, bytes[offset])));
+        }
+
+        if let Some(first) = bytes.iter().find(|c| c.is_ascii_alphabetic()) {
+            if let Some(offset) = bytes.iter().position(|c| c.is_ascii_alphabetic() && c.is_ascii_uppercase() != first.is_ascii_uppercase()) {
+                return Err(Bech32Error::MixedCase(offset));
+            }
+        }
+
+        let separator = bytes.iter().rposition(|c| b'1' == *c).ok_or(Bech32Error::MissingSeparator)?;
+        if 0 == separator {
+            return Err(Bech32Error::InvalidHrp(0));
+        }
+        if 6 > bytes.len() - separator - 1 {
+            return Err(Bech32Error::Decode(DecodeError::InvalidLength(bytes.len())));
+        }
+
+        let hrp = text[..separator].to_ascii_lowercase();
+
+        let mut values = Vec::with_capacity(bytes.len() - separator - 1);
+        for (offset, c) in bytes.iter().enumerate().skip(separator + 1) {
+            match imp::BECH32_REVERSE[*c as usize] {
+                0xff => return Err(Bech32Error::Decode(DecodeError::InvalidSymbol(offset, *c))),
+                v => values.push(v),
+            }
+        }
+
+        if self.constant != imp::polymod(hrp.as_bytes(), &values) {
+            return Err(Bech32Error::Decode(DecodeError::InvalidChecksum(bytes.len())));
+        }
+
+        values.truncate(values.len() - 6);
+        Ok((hrp, values))
+    }
+}
+
+///
+/// the offset is the index of the offending byte in the text, or in the hrp on encoding,
+/// `InvalidValue` carries the index of the offending 5 bit value on encoding
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Bech32Error {
+    InvalidHrp(usize),
+    MissingSeparator,
+    MixedCase(usize),
+    InvalidValue(usize),
+    Decode(DecodeError),
+}
+
+impl Display for Bech32Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Bech32Error::InvalidHrp(offset) => write!(f, "invalid hrp at offset {}", offset),
+            Bech32Error::MissingSeparator => write!(f, "missing separator '1'"),
+            Bech32Error::MixedCase(offset) => write!(f, "mixed case at offset {}", offset),
+            Bech32Error::InvalidValue(index) => write!(f, "invalid 5 bit value at index {}", index),
+            Bech32Error::Decode(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for Bech32Error {}
+
+mod imp {
+    use alloc::vec::Vec;
+
+    use crate::reverse;
+
+    pub const BECH32_ALPHABET: [u8; 32] = *b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+    pub const BECH32_REVERSE: [u8; 256] = reverse(&BECH32_ALPHABET, 32, true);
+
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+    ///
+    /// the bch code over the expanded hrp followed by the values
+    ///
+    pub fn polymod(hrp: &[u8], values: &[u8]) -> u32 {
+        let expanded = hrp.iter().map(|c| c >> 5).chain([0]).chain(hrp.iter().map(|c| c & 0b00011111));
+
+        let mut chk = 1u32;
+        for v in expanded.chain(values.iter().copied()) {
+            let top = chk >> 25;
+            chk = ((chk & 0x1ffffff) << 5) ^ v as u32;
+            for (i, g) in GENERATOR.iter().enumerate() {
+                if 0 != (top >> i) & 1 {
+                    chk ^= g;
+                }
+            }
+        }
+        chk
+    }
+
+    pub fn checksum(hrp: &[u8], values: &[u8], constant: u32) -> [u8; 6] {
+        let mut padded = values.to_vec();
+        padded.extend_from_slice(&[0; 6]);
+        let chk = polymod(hrp, &padded) ^ constant;
+        [0, 1, 2, 3, 4, 5].map(|i| (chk >> (5 * (5 - i))) as u8 & 0b00011111)
+    }
+
+    ///
+    /// regroup the values of `from` bits into the values of `to` bits, the last one padded with zeros
+    ///
+    pub fn regroup(values: &[u8], from: u32, to: u32) -> Vec<u8> {
+        let mut result = Vec::with_capacity((values.len() * from as usize).div_ceil(to as usize));
+        let mut acc = 0u32;
+        let mut bits = 0;
+        for v in values {
+            acc = (acc << from) | *v as u32;
+            bits += from;
+            while to <= bits {
+                bits -= to;
+                result.push((acc >> bits) as u8 & ((1 << to) - 1) as u8);
+            }
+        }
+        if 0 < bits {
+            result.push((acc << (to - bits)) as u8 & ((1 << to) - 1) as u8);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        #[rustfmt::skip]
+        let bech32 = [
+            "A12UEL5L",
+            "a12uel5l",
+            "an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1tt5tgs",
+            "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+            "11qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqc8247j",
+            "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w",
+            "?1ezyfcl",
+        ];
+        #[rustfmt::skip]
+        let bech32m = [
+            "A1LQFN3A",
+            "a1lqfn3a",
+            "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx",
+            "?1v759aa",
+            "split1checkupstagehandshakeupstreamerranterredcaperredlc445v",
+        ];
+        for (variant, other, cases) in [(Bech32::BECH32, Bech32::BECH32M, &bech32[..]), (Bech32::BECH32M, Bech32::BECH32, &bech32m[..])] {
+            for text in cases {
+                let (hrp, values) = variant.decode_5bit(text).unwrap();
+                assert_eq!(variant.encode_5bit(&hrp, &values).unwrap(), text.to_ascii_lowercase());
+                assert_eq!(other.decode_5bit(text), Err(Bech32Error::Decode(DecodeError::InvalidChecksum(text.len()))));
+            }
+        }
+
+        assert_eq!(Bech32::BECH32.encode("juxt", b"juxt_basex").unwrap(), "juxt1df6hsazlvfshxetcenrwul");
+        assert_eq!(Bech32::BECH32M.encode("JUXT", b"juxt_basex").unwrap(), "juxt1df6hsazlvfshxetcv0nzea");
+        assert_eq!(Bech32::BECH32M.decode("JUXT1DF6HSAZLVFSHXETCV0NZEA").unwrap(), ("juxt".into(), b"juxt_basex".to_vec()));
+
+        let bytes: Vec<u8> = (0..32).collect();
+        let age = "age1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0savhh7m";
+        assert_eq!(Bech32::BECH32.encode("age", &bytes).unwrap(), age);
+        assert_eq!(Bech32::BECH32.decode(age).unwrap(), ("age".into(), bytes.clone()));
+        for len in 0..bytes.len() {
+            assert_eq!(Bech32::BECH32.decode(&Bech32::BECH32.encode("a", &bytes[..len]).unwrap()).unwrap().1, &bytes[..len]);
+        }
+    }
+
+    #[test]
+    fn error_works() {
+        assert_eq!(Bech32::BECH32.decode_5bit("x1b4n0q5v"), Err(Bech32Error::Decode(DecodeError::InvalidSymbol(2, b'b'))));
+        assert_eq!(Bech32::BECH32.decode_5bit("li1dgmt3"), Err(Bech32Error::Decode(DecodeError::InvalidLength(8))));
+        assert_eq!(Bech32::BECH32.decode_5bit("de1lg7wt\u{ff}"), Err(Bech32Error::Decode(DecodeError::InvalidSymbol(8, 0xc3))));
+        assert_eq!(Bech32::BECH32.decode_5bit(" 1nwldj5"), Err(Bech32Error::Decode(DecodeError::InvalidSymbol(0, b' '))));
+        assert_eq!(Bech32::BECH32.decode_5bit("A1G7SGD8"), Err(Bech32Error::Decode(DecodeError::InvalidChecksum(8))));
+        assert_eq!(Bech32::BECH32.decode_5bit("10a06t8"), Err(Bech32Error::InvalidHrp(0)));
+        assert_eq!(Bech32::BECH32.decode_5bit("1qzzfhee"), Err(Bech32Error::InvalidHrp(0)));
+        assert_eq!(Bech32::BECH32.decode_5bit("a12UEL5L"), Err(Bech32Error::MixedCase(3)));
+
+        assert_eq!(Bech32::BECH32M.decode_5bit("qyrz8wqd2c9m"), Err(Bech32Error::MissingSeparator));
+        assert_eq!(Bech32::BECH32M.decode_5bit("lt1igcx5c0"), Err(Bech32Error::Decode(DecodeError::InvalidSymbol(3, b'i'))));
+        assert_eq!(Bech32::BECH32M.decode_5bit("in1muywd"), Err(Bech32Error::Decode(DecodeError::InvalidLength(8))));
+        assert_eq!(Bech32::BECH32M.decode_5bit("mm1crxm3i"), Err(Bech32Error::Decode(DecodeError::InvalidSymbol(8, b'i'))));
+        assert_eq!(Bech32::BECH32M.decode_5bit("au1s5cgom"), Err(Bech32Error::Decode(DecodeError::InvalidSymbol(7, b'o'))));
+        assert_eq!(Bech32::BECH32M.decode_5bit("M1VUXWEZ"), Err(Bech32Error::Decode(DecodeError::InvalidChecksum(8))));
+
+        let text = Bech32::BECH32.encode_5bit("a", &[0b11111]).unwrap();
+        assert_eq!(Bech32::BECH32.decode(&text), Err(Bech32Error::Decode(DecodeError::InvalidLength(3))));
+        let text = Bech32::BECH32.encode_5bit("a", &[0b11111, 0b11111]).unwrap();
+        assert_eq!(Bech32::BECH32.decode(&text), Err(Bech32Error::Decode(DecodeError::TrailingBits(3))));
+
+        assert_eq!(Bech32::BECH32.encode("", b""), Err(Bech32Error::InvalidHrp(0)));
+        assert_eq!(Bech32::BECH32.encode("a b", b""), Err(Bech32Error::InvalidHrp(1)));
+        assert_eq!(Bech32::BECH32.encode_5bit("a", &[0, 31, 32, 33]), Err(Bech32Error::InvalidValue(2)));
+        assert_eq!(Bech32::BECH32M.encode_5bit("a", &[0xff]), Err(Bech32Error::InvalidValue(0)));
+        assert_eq!(Bech32Error::MixedCase(3).to_string(), "mixed case at offset 3");
+        assert_eq!(Bech32Error::InvalidValue(2).to_string(), "invalid 5 bit value at index 2");
+    }
+}
@@ -1,6 +1,49 @@
-use std::error::Error;
-use std::fmt::Display;
-use std::str::FromStr;
+#![no_std]
+
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::Display;
+use core::str::FromStr;
+
+mod base16;
+mod base32;
+mod base58;
+mod base85;
+mod bech32;
+mod pem;
+mod radix;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod simd;
+#[cfg(feature = "std")]
+mod stream;
+
+pub use base16::Base16;
+pub use base32::Base32;
+pub use base32::Base32Crockford;
+pub use base32::Base32Hex;
+pub use base58::Base58;
+pub use base58::Base58Check;
+pub use base85::Ascii85;
+pub use base85::Base85Error;
+pub use base85::Z85;
+pub use bech32::Bech32;
+pub use bech32::Bech32Error;
+pub use pem::Pem;
+pub use pem::PemError;
+pub use radix::BaseX;
+#[cfg(feature = "std")]
+pub use stream::Base64Reader;
+#[cfg(feature = "std")]
+pub use stream::Base64Writer;
 
 #[derive(Debug)]
 pub struct Base64(Vec<u8>);
@@ -20,7 +63,7 @@ impl FromStr for Base64 {
 }
 
 impl Display for Base64 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         self.0.iter().for_each(|c| {
             write!(f, "{}", imp::BASE64_ALPHABET[*c as usize] as char).unwrap();
         });
@@ -32,9 +75,431 @@ impl Base64 {
     pub fn to_string_safe(&self) -> String {
         String::from_iter(self.0.iter().map(|c| imp::BASE64_ALPHABET_SAFE[*c as usize] as char))
     }
+
+    ///
+    /// decode with the standard alphabet, the `=` padding is optional but must be correct if present
+    ///
+    pub fn decode(text: &str) -> Result<Vec<u8>, DecodeError> {
+        Base64Config::STANDARD.padding(Padding::Indifferent).decode(text)
+    }
+
+    ///
+    /// decode with the url and filename safe alphabet, the `=` padding is optional but must be correct if present
+    ///
+    pub fn decode_safe(text: &str) -> Result<Vec<u8>, DecodeError> {
+        Base64Config::URL_SAFE.padding(Padding::Indifferent).decode(text)
+    }
+
+    pub fn to_string_with(&self, config: &Base64Config) -> String {
+        String::from_iter(self.0.iter().filter(|c| 64 > **c || Padding::Never != config.padding).map(|c| config.alphabet[*c as usize] as char))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Padding {
+    /// pad on encode, require the padding on decode
+    Always,
+    /// no padding on encode, reject the padding on decode
+    Never,
+    /// pad on encode, the padding is optional but must be correct if present on decode
+    Indifferent,
+}
+
+///
+/// alphabet, padding and trailing bits policy of base64
+///
+/// ```rust
+/// use juxt_basex::Base64Config;
+/// use juxt_basex::Padding;
+///
+/// let jwt = Base64Config::URL_SAFE.padding(Padding::Never);
+/// assert_eq!(jwt.encode(b"\xfb\xff"), "-_8");
+/// assert_eq!(jwt.decode("-_8").unwrap(), b"\xfb\xff");
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Base64Config {
+    alphabet: [u8; 65],
+    reverse: [u8; 256],
+    padding: Padding,
+    reject_trailing_bits: bool,
+    line_width: usize,
+    line_ending: LineEnding,
+    ignore_whitespace: bool,
+    constant_time: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineEnding {
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+    pub const fn as_bytes(&self) -> &'static [u8] {
+        match self {
+            LineEnding::Lf => b"\n",
+            LineEnding::CrLf => b"\r\n",
+        }
+    }
+}
+
+impl Base64Config {
+    pub const STANDARD: Base64Config = Self::from_alphabet(imp::BASE64_ALPHABET);
+
+    pub const URL_SAFE: Base64Config = Self::from_alphabet(imp::BASE64_ALPHABET_SAFE);
+
+    pub const URL_SAFE_NO_PAD: Base64Config = Self::URL_SAFE.padding(Padding::Never);
+
+    ///
+    /// rfc2045, lines of 76 symbols with crlf
+    ///
+    pub const MIME: Base64Config = Self::STANDARD.line_wrap(76, LineEnding::CrLf).ignore_whitespace(true);
+
+    ///
+    /// rfc7468, lines of 64 symbols with lf
+    ///
+    pub const PEM: Base64Config = Self::STANDARD.line_wrap(64, LineEnding::Lf).ignore_whitespace(true);
+
+    const fn from_alphabet(alphabet: [u8; 65]) -> Self {
+        Self {
+            alphabet,
+            reverse: reverse(&alphabet, 64, false),
+            padding: Padding::Always,
+            reject_trailing_bits: true,
+            line_width: 0,
+            line_ending: LineEnding::Lf,
+            ignore_whitespace: false,
+            constant_time: false,
+        }
+    }
+
+    ///
+    /// custom alphabet of 64 unique ascii graphic symbols, `=` is reserved for the padding
+    ///
+    pub fn new(alphabet: &str) -> Result<Self, AlphabetError> {
+        let symbols: Vec<char> = alphabet.chars().collect();
+        if 64 != symbols.len() {
+            return Err(AlphabetError::InvalidLength(symbols.len()));
+        }
+
+        let mut table = [b'='; 65];
+        for (i, c) in symbols.iter().enumerate() {
+            if !c.is_ascii_graphic() || '=' == *c {
+                return Err(AlphabetError::InvalidSymbol(i, *c));
+            }
+            if table[..i].contains(&(*c as u8)) {
+                return Err(AlphabetError::DuplicateSymbol(i, *c));
+            }
+            table[i] = *c as u8;
+        }
+
+        Ok(Self::from_alphabet(table))
+    }
+
+    pub const fn padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    ///
+    /// reject the non zero unused bits of the last symbol, so each bytes has exactly one encoding
+    ///
+    pub const fn reject_trailing_bits(mut self, reject: bool) -> Self {
+        self.reject_trailing_bits = reject;
+        self
+    }
+
+    ///
+    /// break the encoded text into lines of `width` symbols, rounded down to a multiple of 4, so 76 of rfc2045 stays 76,
+    /// 0 to 3 for no wrapping, there is no line ending after the last line
+    ///
+    pub const fn line_wrap(mut self, width: usize, ending: LineEnding) -> Self {
+        self.line_width = width / 4 * 4;
+        self.line_ending = ending;
+        self
+    }
+
+    ///
+    /// skip the ascii whitespace on decode, the error offsets still point into the original text
+    ///
+    pub const fn ignore_whitespace(mut self, ignore: bool) -> Self {
+        self.ignore_whitespace = ignore;
+        self
+    }
+
+    ///
+    /// decode the symbols without the secret dependent branches and table indexing, for the keys and the tokens,
+    /// an invalid input still takes the early exit, only its position leaks
+    ///
+    pub const fn constant_time(mut self, constant_time: bool) -> Self {
+        self.constant_time = constant_time;
+        self
+    }
+
+    ///
+    /// symbol -> index, `0xff` for the symbols not in the alphabet
+    ///
+    pub(crate) fn lookup(&self, c: u8) -> u8 {
+        if self.constant_time {
+            imp::lookup_ct(c, &self.alphabet[..64])
+        } else {
+            self.reverse[c as usize]
+        }
+    }
+
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        let mut out = String::new();
+        self.encode_string(bytes, &mut out);
+        out
+    }
+
+    ///
+    /// append to `out`, reserve once and no other allocation
+    ///
+    pub fn encode_string(&self, bytes: &[u8], out: &mut String) {
+        let start = out.len();
+        let len = self.encoded_len(bytes.len());
+
+        // the buffer is moved out and back, no copy, the alphabet and the line endings are ascii
+        let mut buffer = core::mem::take(out).into_bytes();
+        buffer.resize(start + len, 0x00);
+        self.encode_wrapped(bytes, &mut buffer[start..]);
+        *out = String::from_utf8(buffer).expect("the alphabet is checked to be ascii by Base64Config::new");
+    }
+
+    fn encode_wrapped(&self, bytes: &[u8], out: &mut [u8]) -> usize {
+        let pad = Padding::Never != self.padding;
+        if 0 == self.line_width {
+            return imp::encode_to_slice(bytes, &self.alphabet, pad, out);
+        }
+
+        let ending = self.line_ending.as_bytes();
+        let mut n = 0;
+        for (i, line) in bytes.chunks(self.line_width / 4 * 3).enumerate() {
+            if 0 < i {
+                out[n..n + ending.len()].copy_from_slice(ending);
+                n += ending.len();
+            }
+            n += imp::encode_to_slice(line, &self.alphabet, pad, &mut out[n..]);
+        }
+        n
+    }
+
+    ///
+    /// return the count of bytes written to `out`
+    ///
+    pub fn encode_to_slice(&self, bytes: &[u8], out: &mut [u8]) -> Result<usize, SliceError> {
+        let len = self.encoded_len(bytes.len());
+        if out.len() < len {
+            return Err(SliceError::OutputTooSmall(len));
+        }
+
+        Ok(self.encode_wrapped(bytes, out))
+    }
+
+    pub fn decode(&self, text: &str) -> Result<Vec<u8>, DecodeError> {
+        let text = text.as_bytes();
+        let symbols = self.symbols(text);
+
+        let decode = || {
+            let end = imp::validate(&symbols, |c| self.lookup(c), self.padding)?;
+            let mut out = vec![0x00; imp::decoded_len(end)];
+            self.decode_symbols(&symbols[..end], &mut out)?;
+            Ok(out)
+        };
+
+        decode().map_err(|e: DecodeError| imp::locate(e, text, &symbols))
+    }
+
+    ///
+    /// return the count of bytes written to `out`, allocate only when whitespace is ignored and present
+    ///
+    pub fn decode_to_slice(&self, text: &str, out: &mut [u8]) -> Result<usize, SliceError> {
+        let text = text.as_bytes();
+        let symbols = self.symbols(text);
+
+        let mut decode = || {
+            let end = imp::validate(&symbols, |c| self.lookup(c), self.padding)?;
+
+            let len = imp::decoded_len(end);
+            if out.len() < len {
+                return Err(SliceError::OutputTooSmall(len));
+            }
+
+            Ok(self.decode_symbols(&symbols[..end], out)?)
+        };
+
+        decode().map_err(|e| match e {
+            SliceError::Decode(e) => SliceError::Decode(imp::locate(e, text, &symbols)),
+            _ => e,
+        })
+    }
+
+    ///
+    /// decode the validated `text` without the padding, through the fast paths unless in constant time
+    ///
+    pub(crate) fn decode_symbols(&self, text: &[u8], out: &mut [u8]) -> Result<usize, DecodeError> {
+        let (read, written) = if self.constant_time { (0, 0) } else { simd::decode(text, &self.alphabet, &self.reverse, out) };
+        let n = imp::decode_to_slice(&text[read..], |c| self.lookup(c), self.reject_trailing_bits, &mut out[written..]).map_err(|e| e.map_offset(|o| o + read))?;
+        Ok(written + n)
+    }
+
+    fn symbols<'a>(&self, text: &'a [u8]) -> Cow<'a, [u8]> {
+        if self.ignore_whitespace && text.iter().any(|c| c.is_ascii_whitespace()) {
+            Cow::Owned(text.iter().filter(|c| !c.is_ascii_whitespace()).copied().collect())
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+
+    ///
+    /// the exact count of symbols and line endings for `bytes_len` bytes
+    ///
+    pub fn encoded_len(&self, bytes_len: usize) -> usize {
+        let len = if Padding::Never == self.padding { bytes_len / 3 * 4 + (bytes_len % 3 * 4).div_ceil(3) } else { bytes_len.div_ceil(3) * 4 };
+
+        if 0 == self.line_width || 0 == len {
+            len
+        } else {
+            len + (len - 1) / self.line_width * self.line_ending.as_bytes().len()
+        }
+    }
+
+    ///
+    /// the upper bound of bytes for `text_len` symbols, exact if there is no padding
+    ///
+    pub fn decoded_len(&self, text_len: usize) -> usize {
+        imp::decoded_len(text_len)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SliceError {
+    /// the required length of the output
+    OutputTooSmall(usize),
+    Decode(DecodeError),
+}
+
+impl From<DecodeError> for SliceError {
+    fn from(value: DecodeError) -> Self {
+        SliceError::Decode(value)
+    }
+}
+
+impl Display for SliceError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SliceError::OutputTooSmall(len) => write!(f, "output too small, {} bytes required", len),
+            SliceError::Decode(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for SliceError {}
+
+///
+/// the offset is the index of the offending symbol in the alphabet
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AlphabetError {
+    InvalidLength(usize),
+    InvalidSymbol(usize, char),
+    DuplicateSymbol(usize, char),
+}
+
+impl Display for AlphabetError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AlphabetError::InvalidLength(len) => write!(f, "invalid alphabet length {}", len),
+            AlphabetError::InvalidSymbol(offset, c) => write!(f, "invalid alphabet symbol {:?} at offset {}", c, offset),
+            AlphabetError::DuplicateSymbol(offset, c) => write!(f, "duplicate alphabet symbol {:?} at offset {}", c, offset),
+        }
+    }
+}
+
+impl Error for AlphabetError {}
+
+///
+/// the offset is the index of the offending byte in the input text
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    InvalidSymbol(usize, u8),
+    InvalidPadding(usize),
+    InvalidLength(usize),
+    TrailingBits(usize),
+    InvalidChecksum(usize),
+}
+
+impl DecodeError {
+    pub fn offset(&self) -> usize {
+        match self {
+            DecodeError::InvalidSymbol(offset, _) => *offset,
+            DecodeError::InvalidPadding(offset) => *offset,
+            DecodeError::InvalidLength(offset) => *offset,
+            DecodeError::TrailingBits(offset) => *offset,
+            DecodeError::InvalidChecksum(offset) => *offset,
+        }
+    }
+
+    ///
+    /// map the offset into the original input, for the input decoded piece by piece or filtered
+    ///
+    pub(crate) fn map_offset<F: FnOnce(usize) -> usize>(self, f: F) -> Self {
+        match self {
+            DecodeError::InvalidSymbol(offset, byte) => DecodeError::InvalidSymbol(f(offset), byte),
+            DecodeError::InvalidPadding(offset) => DecodeError::InvalidPadding(f(offset)),
+            DecodeError::InvalidLength(offset) => DecodeError::InvalidLength(f(offset)),
+            DecodeError::TrailingBits(offset) => DecodeError::TrailingBits(f(offset)),
+            DecodeError::InvalidChecksum(offset) => DecodeError::InvalidChecksum(f(offset)),
+        }
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DecodeError::InvalidSymbol(offset, byte) => write!(f, "invalid symbol {:#04x} at offset {}", byte, offset),
+            DecodeError::InvalidPadding(offset) => write!(f, "invalid padding at offset {}", offset),
+            DecodeError::InvalidLength(offset) => write!(f, "invalid length, input ends at offset {}", offset),
+            DecodeError::TrailingBits(offset) => write!(f, "non zero trailing bits at offset {}", offset),
+            DecodeError::InvalidChecksum(offset) => write!(f, "invalid checksum, input ends at offset {}", offset),
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+///
+/// symbol -> index of the first `len` symbols, `0xff` for the symbols not in the alphabet,
+/// both cases map to the same index if `ignore_case`
+///
+const fn reverse<const N: usize>(alphabet: &[u8; N], len: usize, ignore_case: bool) -> [u8; 256] {
+    let mut table = [0xff; 256];
+    let mut i = 0;
+    while len > i {
+        table[alphabet[i] as usize] = i as u8;
+        if ignore_case {
+            table[alphabet[i].to_ascii_lowercase() as usize] = i as u8;
+            table[alphabet[i].to_ascii_uppercase() as usize] = i as u8;
+        }
+        i += 1;
+    }
+    table
 }
 
 mod imp {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use super::simd;
+    use super::DecodeError;
+    use super::Padding;
+
     #[rustfmt::skip]
     pub const BASE64_ALPHABET: [u8; 65] = [
         b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', b'P',
@@ -53,36 +518,190 @@ mod imp {
         b'=',
     ];
 
+    ///
+    /// the alphabet of the indices themselves, for `Base64`
+    ///
+    const BASE64_INDICES: [u8; 65] = {
+        let mut table = [0x00; 65];
+        let mut i = 0;
+        while 65 > i {
+            table[i] = i as u8;
+            i += 1;
+        }
+        table
+    };
+
     pub fn to_base64(bytes: &[u8]) -> Vec<u8> {
-        let mut result = Vec::with_capacity((bytes.len() + 2) / 3 * 4);
-        for block in bytes.chunks(3) {
-            match block.len() {
-                1 => {
-                    result.push(block[0] >> 2);
-                    result.push((block[0] & 0b00000011) << 4);
-                    result.push(64);
-                    result.push(64);
+        let mut result = vec![0x00; bytes.len().div_ceil(3) * 4];
+        encode_to_slice(bytes, &BASE64_INDICES, true, &mut result);
+        result
+    }
+
+    pub fn encode_to_slice(bytes: &[u8], alphabet: &[u8; 65], pad: bool, out: &mut [u8]) -> usize {
+        let (read, mut n) = simd::encode(bytes, alphabet, out);
+        let mut blocks = bytes[read..].chunks_exact(3);
+
+        for (block, out) in blocks.by_ref().zip(out[n..].chunks_exact_mut(4)) {
+            out[0] = alphabet[(block[0] >> 2) as usize];
+            out[1] = alphabet[(((block[0] & 0b00000011) << 4) | (block[1] >> 4)) as usize];
+            out[2] = alphabet[(((block[1] & 0b00001111) << 2) | (block[2] >> 6)) as usize];
+            out[3] = alphabet[(block[2] & 0b00111111) as usize];
+            n += 4;
+        }
+
+        match blocks.remainder() {
+            [b0] => {
+                out[n] = alphabet[(b0 >> 2) as usize];
+                out[n + 1] = alphabet[((b0 & 0b00000011) << 4) as usize];
+                n += 2;
+                if pad {
+                    out[n] = alphabet[64];
+                    out[n + 1] = alphabet[64];
+                    n += 2;
                 }
-                2 => {
-                    result.push(block[0] >> 2);
-                    result.push(((block[0] & 0b00000011) << 4) | (block[1] >> 4));
-                    result.push((block[1] & 0b00001111) << 2);
-                    result.push(64);
+            }
+            [b0, b1] => {
+                out[n] = alphabet[(b0 >> 2) as usize];
+                out[n + 1] = alphabet[(((b0 & 0b00000011) << 4) | (b1 >> 4)) as usize];
+                out[n + 2] = alphabet[((b1 & 0b00001111) << 2) as usize];
+                n += 3;
+                if pad {
+                    out[n] = alphabet[64];
+                    n += 1;
+                }
+            }
+            _ => {}
+        }
+
+        n
+    }
+
+    ///
+    /// check the symbols and the padding, return the length without the padding
+    ///
+    pub fn validate<F: Fn(u8) -> u8>(text: &[u8], lookup: F, padding: Padding) -> Result<usize, DecodeError> {
+        let mut end = text.len();
+        while 0 < end && b'=' == text[end - 1] {
+            end -= 1;
+        }
+
+        let pads = text.len() - end;
+        let malformed = 0 < pads && (!text.len().is_multiple_of(4) || end.is_multiple_of(4) || pads != 4 - end % 4);
+        let unexpected = 0 < pads && Padding::Never == padding;
+        let missing = 0 == pads && !end.is_multiple_of(4) && Padding::Always == padding;
+        let e = if malformed || unexpected || missing {
+            DecodeError::InvalidPadding(end)
+        } else if 1 == end % 4 {
+            DecodeError::InvalidLength(end)
+        } else {
+            // the symbols are checked on decode
+            return Ok(end);
+        };
+
+        // the invalid symbols come first
+        Err(invalid(&text[..end], lookup).unwrap_or(e))
+    }
+
+    ///
+    /// the first invalid symbol, a `=` is a misplaced padding
+    ///
+    pub fn invalid<F: Fn(u8) -> u8>(text: &[u8], lookup: F) -> Option<DecodeError> {
+        let offset = text.iter().position(|c| 0xff == lookup(*c))?;
+        let c = text[offset];
+        Some(if b'=' == c { DecodeError::InvalidPadding(offset) } else { DecodeError::InvalidSymbol(offset, c) })
+    }
+
+    ///
+    /// map the offset into `symbols`, which is `text` without whitespace, back into `text`
+    ///
+    pub fn locate(e: DecodeError, text: &[u8], symbols: &[u8]) -> DecodeError {
+        if text.len() == symbols.len() {
+            return e;
+        }
+
+        e.map_offset(|offset| {
+            let mut positions = text.iter().enumerate().filter(|(_, c)| !c.is_ascii_whitespace()).map(|(i, _)| i);
+            match positions.nth(offset) {
+                Some(i) => i,
+                None => text.iter().rposition(|c| !c.is_ascii_whitespace()).map_or(0, |i| i + 1),
+            }
+        })
+    }
+
+    ///
+    /// the index of `c` in `alphabet`, `0xff` if absent, scan the whole alphabet with masks instead of branches
+    ///
+    pub fn lookup_ct(c: u8, alphabet: &[u8]) -> u8 {
+        let mut index = 0xff;
+        for (i, a) in alphabet.iter().enumerate() {
+            // 0xff if equal, 0x00 otherwise
+            let mask = ((((c ^ a) as u32).wrapping_sub(1)) >> 8) as u8;
+            index = (index & !mask) | (i as u8 & mask);
+        }
+        index
+    }
+
+    ///
+    /// the count of bytes decoded from `len` symbols without the padding
+    ///
+    pub fn decoded_len(len: usize) -> usize {
+        len / 4 * 3 + (len % 4) * 3 / 4
+    }
+
+    ///
+    /// `text` must be validated and without the padding, `out` must be large enough, the symbols are checked here
+    ///
+    pub fn decode_to_slice<F: Fn(u8) -> u8>(text: &[u8], lookup: F, reject_trailing_bits: bool, out: &mut [u8]) -> Result<usize, DecodeError> {
+        let mut blocks = text.chunks_exact(4);
+        let mut n = 0;
+
+        for (block, out) in blocks.by_ref().zip(out.chunks_exact_mut(3)) {
+            let indices = [lookup(block[0]), lookup(block[1]), lookup(block[2]), lookup(block[3])];
+            // 0xff for the invalid symbols, the valid ones are less than 64
+            if 0 != (indices[0] | indices[1] | indices[2] | indices[3]) & 0x80 {
+                return Err(invalid(block, &lookup).unwrap().map_offset(|o| o + n / 3 * 4));
+            }
+            out[0] = (indices[0] << 2) | (indices[1] >> 4);
+            out[1] = (indices[1] << 4) | (indices[2] >> 2);
+            out[2] = (indices[2] << 6) | indices[3];
+            n += 3;
+        }
+
+        let rest = blocks.remainder();
+        if let Some(e) = invalid(rest, &lookup) {
+            return Err(e.map_offset(|o| o + text.len() - rest.len()));
+        }
+
+        match rest {
+            [c0, c1] => {
+                let indices = [lookup(*c0), lookup(*c1)];
+                if reject_trailing_bits && 0 != indices[1] & 0b00001111 {
+                    return Err(DecodeError::TrailingBits(text.len() - 1));
                 }
-                _ => {
-                    result.push(block[0] >> 2);
-                    result.push(((block[0] & 0b00000011) << 4) | (block[1] >> 4));
-                    result.push(((block[1] & 0b00001111) << 2) | (block[2] >> 6));
-                    result.push(block[2] & 0b00111111);
+                out[n] = (indices[0] << 2) | (indices[1] >> 4);
+                n += 1;
+            }
+            [c0, c1, c2] => {
+                let indices = [lookup(*c0), lookup(*c1), lookup(*c2)];
+                if reject_trailing_bits && 0 != indices[2] & 0b00000011 {
+                    return Err(DecodeError::TrailingBits(text.len() - 1));
                 }
+                out[n] = (indices[0] << 2) | (indices[1] >> 4);
+                out[n + 1] = (indices[1] << 4) | (indices[2] >> 2);
+                n += 2;
             }
+            _ => {}
         }
-        result
+
+        Ok(n)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use alloc::format;
+    use alloc::string::ToString;
+
     use super::*;
 
     #[test]
@@ -92,4 +711,219 @@ mod tests {
         assert_eq!(Base64::from_str("foob").unwrap().to_string(), "Zm9vYg==");
         assert_eq!(format!("{:02x?}", Base64::from("fo".as_bytes())), "Base64([19, 26, 3c, 40])");
     }
+
+    #[test]
+    fn decode_works() {
+        for text in ["", "f", "fo", "foo", "foob", "fooba", "foobar"] {
+            assert_eq!(Base64::decode(&Base64::from_str(text).unwrap().to_string()).unwrap(), text.as_bytes());
+        }
+
+        let bytes: Vec<u8> = (0..=255).collect();
+        for len in 0..bytes.len() {
+            let base64 = Base64::from(&bytes[..len]);
+            assert_eq!(Base64::decode(&base64.to_string()).unwrap(), &bytes[..len]);
+            assert_eq!(Base64::decode_safe(&base64.to_string_safe()).unwrap(), &bytes[..len]);
+            assert_eq!(Base64::decode(base64.to_string().trim_end_matches('=')).unwrap(), &bytes[..len]);
+        }
+
+        assert_eq!(Base64::decode("Zm9v-_8="), Err(DecodeError::InvalidSymbol(4, b'-')));
+        assert_eq!(Base64::decode_safe("Zm9v+/8="), Err(DecodeError::InvalidSymbol(4, b'+')));
+        assert_eq!(Base64::decode("Zm9vY"), Err(DecodeError::InvalidLength(5)));
+        assert_eq!(Base64::decode("Zg="), Err(DecodeError::InvalidPadding(2)));
+        assert_eq!(Base64::decode("Zg==="), Err(DecodeError::InvalidPadding(2)));
+        assert_eq!(Base64::decode("Zm9v===="), Err(DecodeError::InvalidPadding(4)));
+        assert_eq!(Base64::decode("Zm=vYg=="), Err(DecodeError::InvalidPadding(2)));
+        assert_eq!(Base64::decode("Zh=="), Err(DecodeError::TrailingBits(1)));
+        assert_eq!(Base64::decode("Zm9="), Err(DecodeError::TrailingBits(2)));
+        assert_eq!(Base64::decode("Zm9v*").unwrap_err().offset(), 4);
+        assert_eq!(Base64::decode("Zm9v*").unwrap_err().to_string(), "invalid symbol 0x2a at offset 4");
+    }
+
+    #[test]
+    fn config_works() {
+        assert_eq!(Base64Config::STANDARD.encode(b"\xfb\xff"), "+/8=");
+        assert_eq!(Base64Config::URL_SAFE.encode(b"\xfb\xff"), "-_8=");
+        assert_eq!(Base64Config::URL_SAFE_NO_PAD.encode(b"\xfb\xff"), "-_8");
+        assert_eq!(Base64Config::STANDARD.padding(Padding::Indifferent).encode(b"\xfb\xff"), "+/8=");
+        assert_eq!(Base64::from("f".as_bytes()).to_string_with(&Base64Config::URL_SAFE_NO_PAD), "Zg");
+
+        assert_eq!(Base64Config::STANDARD.decode("+/8="), Ok(b"\xfb\xff".to_vec()));
+        assert_eq!(Base64Config::STANDARD.decode("+/8"), Err(DecodeError::InvalidPadding(3)));
+        assert_eq!(Base64Config::URL_SAFE_NO_PAD.decode("-_8"), Ok(b"\xfb\xff".to_vec()));
+        assert_eq!(Base64Config::URL_SAFE_NO_PAD.decode("-_8="), Err(DecodeError::InvalidPadding(3)));
+        assert_eq!(Base64Config::URL_SAFE.padding(Padding::Indifferent).decode("-_8"), Ok(b"\xfb\xff".to_vec()));
+        assert_eq!(Base64Config::URL_SAFE.padding(Padding::Indifferent).decode("-_8="), Ok(b"\xfb\xff".to_vec()));
+
+        assert_eq!(Base64Config::STANDARD.decode("Zh=="), Err(DecodeError::TrailingBits(1)));
+        assert_eq!(Base64Config::STANDARD.reject_trailing_bits(false).decode("Zh=="), Ok(b"f".to_vec()));
+        assert_eq!(Base64Config::STANDARD.reject_trailing_bits(false).decode("Zm9="), Ok(b"fo".to_vec()));
+
+        let crypt = Base64Config::new("./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz").unwrap().padding(Padding::Never);
+        assert_eq!(crypt.encode(b"foobar"), "NaxjMa3m");
+        assert_eq!(crypt.decode("NaxjMa3m").unwrap(), b"foobar");
+
+        assert_eq!(Base64Config::new("ABC"), Err(AlphabetError::InvalidLength(3)));
+        assert_eq!(Base64Config::new(&"A".repeat(64)), Err(AlphabetError::DuplicateSymbol(1, 'A')));
+        assert_eq!(Base64Config::new(&"=".repeat(64)), Err(AlphabetError::InvalidSymbol(0, '=')));
+        assert_eq!(Base64Config::new(&" ".repeat(64)).unwrap_err().to_string(), "invalid alphabet symbol ' ' at offset 0");
+    }
+
+    #[test]
+    fn slice_works() {
+        let bytes: Vec<u8> = (0..=255).cycle().take(2000).collect();
+        let configs = [Base64Config::STANDARD, Base64Config::URL_SAFE_NO_PAD, Base64Config::STANDARD.padding(Padding::Indifferent)];
+
+        for config in configs.iter() {
+            for len in (0..300).chain([767, 768, 769, 2000]) {
+                let expected = Base64::from(&bytes[..len]).to_string_with(config);
+                assert_eq!(config.encoded_len(len), expected.len());
+                assert!(config.decoded_len(expected.len()) >= len);
+
+                let mut out = [0x00; 4000];
+                assert_eq!(config.encode_to_slice(&bytes[..len], &mut out), Ok(expected.len()));
+                assert_eq!(&out[..expected.len()], expected.as_bytes());
+
+                let mut text = String::from("juxt:");
+                config.encode_string(&bytes[..len], &mut text);
+                assert_eq!(text, format!("juxt:{}", expected));
+                assert_eq!(config.encode(&bytes[..len]), expected);
+
+                assert_eq!(config.decode_to_slice(&expected, &mut out), Ok(len));
+                assert_eq!(&out[..len], &bytes[..len]);
+                assert_eq!(config.decode(&expected).unwrap(), &bytes[..len]);
+            }
+        }
+
+        let mut out = [0x00; 4];
+        assert_eq!(Base64Config::STANDARD.encode_to_slice(b"foob", &mut out), Err(SliceError::OutputTooSmall(8)));
+        assert_eq!(Base64Config::STANDARD.decode_to_slice("Zm9vYg==", &mut out[..3]), Err(SliceError::OutputTooSmall(4)));
+        assert_eq!(Base64Config::STANDARD.decode_to_slice("Zm9vYg=", &mut out), Err(SliceError::Decode(DecodeError::InvalidPadding(6))));
+        assert_eq!(Base64Config::STANDARD.decode_to_slice("Zg==", &mut out[..1]), Ok(1));
+        assert_eq!(SliceError::OutputTooSmall(8).to_string(), "output too small, 8 bytes required");
+    }
+
+    #[test]
+    fn constant_time_works() {
+        for c in 0..=255 {
+            assert_eq!(imp::lookup_ct(c, &imp::BASE64_ALPHABET[..64]), reverse(&imp::BASE64_ALPHABET, 64, false)[c as usize]);
+        }
+
+        let bytes: Vec<u8> = (0..=255).collect();
+        for config in [Base64Config::STANDARD, Base64Config::URL_SAFE_NO_PAD, Base64Config::PEM] {
+            let ct = config.constant_time(true);
+            for len in 0..bytes.len() {
+                let text = config.encode(&bytes[..len]);
+                assert_eq!(ct.decode(&text).unwrap(), &bytes[..len]);
+            }
+        }
+
+        let ct = Base64Config::STANDARD.constant_time(true).reject_trailing_bits(true);
+        for text in ["Zm9v*mFy", "Zm9=YmFy", "Zm9vYmF", "Zm9vYm==", "Zm9vYh==", "Zm9vY"] {
+            assert_eq!(ct.decode(text), Base64Config::STANDARD.reject_trailing_bits(true).decode(text));
+            assert!(ct.decode(text).is_err());
+        }
+    }
+
+    #[test]
+    fn simd_works() {
+        // xorshift64
+        let mut seed = 0x2545f4914f6cdd1du64;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed as usize
+        };
+
+        let crypt = Base64Config::new("./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz").unwrap();
+        for _ in 0..1000 {
+            let len = next() % 400;
+            let bytes: Vec<u8> = (0..len).map(|_| next() as u8).collect();
+
+            for config in [Base64Config::STANDARD, Base64Config::URL_SAFE_NO_PAD, crypt] {
+                // the blocks of 3 bytes are too short for the fast paths
+                let expected = String::from_iter(bytes.chunks(3).map(|block| config.encode(block)));
+                assert_eq!(config.encode(&bytes), expected);
+
+                let mut out = vec![0x00; expected.len()];
+                let check_encode = |(read, written): (usize, usize), out: &[u8]| {
+                    assert_eq!(read / 3 * 4, written);
+                    assert_eq!(&out[..written], &expected.as_bytes()[..written]);
+                };
+                check_encode(simd::swar::encode(&bytes, &config.alphabet, &mut out), &out);
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                {
+                    if std::is_x86_feature_detected!("avx2") {
+                        check_encode(unsafe { simd::x86::encode_avx2(&bytes, &config.alphabet, &mut out) }, &out);
+                    }
+                    if std::is_x86_feature_detected!("ssse3") {
+                        check_encode(unsafe { simd::x86::encode_ssse3(&bytes, &config.alphabet, &mut out) }, &out);
+                    }
+                }
+
+                // the constant time mode takes the scalar path only
+                let scalar = config.constant_time(true);
+                assert_eq!(config.decode(&expected).unwrap(), bytes);
+                assert_eq!(scalar.decode(&expected).unwrap(), bytes);
+
+                let text = expected.trim_end_matches('=').as_bytes();
+                let mut out = vec![0x00; len];
+                let check_decode = |(read, written): (usize, usize), out: &[u8]| {
+                    assert_eq!(written / 3 * 4, read);
+                    assert_eq!(&out[..written], &bytes[..written]);
+                };
+                check_decode(simd::swar::decode(text, &config.reverse, &mut out), &out);
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                {
+                    if std::is_x86_feature_detected!("avx2") {
+                        check_decode(unsafe { simd::x86::decode_avx2(text, &config.alphabet, &mut out) }, &out);
+                    }
+                    if std::is_x86_feature_detected!("ssse3") {
+                        check_decode(unsafe { simd::x86::decode_ssse3(text, &config.alphabet, &mut out) }, &out);
+                    }
+                }
+
+                // a random byte at a random offset, the same result from both paths
+                if !expected.is_empty() {
+                    let mut corrupted = expected.clone().into_bytes();
+                    corrupted[next() % expected.len()] = next() as u8 & 0x7f;
+                    let corrupted = String::from_utf8(corrupted).unwrap();
+                    assert_eq!(config.decode(&corrupted), scalar.decode(&corrupted));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn wrap_works() {
+        let bytes: Vec<u8> = (0..=255).collect();
+
+        let mime = Base64Config::MIME.encode(&bytes);
+        assert!(mime.split("\r\n").all(|line| 76 >= line.len()));
+        assert_eq!(mime.split("\r\n").count(), 5);
+        assert_eq!(mime.replace("\r\n", ""), Base64Config::STANDARD.encode(&bytes));
+        assert_eq!(Base64Config::MIME.decode(&mime).unwrap(), bytes);
+
+        for len in 0..bytes.len() {
+            for config in [Base64Config::MIME, Base64Config::PEM, Base64Config::URL_SAFE_NO_PAD.line_wrap(4, LineEnding::Lf).ignore_whitespace(true)] {
+                let text = config.encode(&bytes[..len]);
+                assert_eq!(config.encoded_len(len), text.len());
+                assert!(!text.ends_with('\n'));
+                assert_eq!(config.decode(&text).unwrap(), &bytes[..len]);
+            }
+        }
+
+        assert_eq!(Base64Config::STANDARD.line_wrap(4, LineEnding::Lf).encode(b"foobar"), "Zm9v\nYmFy");
+        assert_eq!(Base64Config::STANDARD.line_wrap(7, LineEnding::Lf).encode(b"foobar"), "Zm9v\nYmFy");
+        assert_eq!(Base64Config::STANDARD.line_wrap(3, LineEnding::Lf).encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(Base64Config::STANDARD.decode("Zm9v\nYmFy"), Err(DecodeError::InvalidSymbol(4, b'\n')));
+        assert_eq!(Base64Config::STANDARD.ignore_whitespace(true).decode(" Zm9v\n\tYmFy \r\n"), Ok(b"foobar".to_vec()));
+        assert_eq!(Base64Config::PEM.decode("Zm9v\n Ym*y"), Err(DecodeError::InvalidSymbol(8, b'*')));
+        assert_eq!(Base64Config::PEM.decode("Zm9v\nYmF \n"), Err(DecodeError::InvalidPadding(8)));
+        assert_eq!(Base64Config::PEM.decode("Zm9v\nY\n"), Err(DecodeError::InvalidPadding(6)));
+
+        let mut out = [0x00; 6];
+        assert_eq!(Base64Config::MIME.decode_to_slice("Zm9v\r\nYmFy", &mut out), Ok(6));
+        assert_eq!(Base64Config::MIME.decode_to_slice("Zm9v\r\nYmFy", &mut out[..5]), Err(SliceError::OutputTooSmall(6)));
+    }
 }
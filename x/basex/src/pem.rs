@@ -0,0 +1,138 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::Display;
+
+use crate::Base64Config;
+use crate::DecodeError;
+
+///
+/// the textual encoding of rfc7468, `-----BEGIN LABEL-----` / `-----END LABEL-----` around base64 lines of 64 symbols
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pem {
+    label: String,
+    contents: Vec<u8>,
+}
+
+impl Display for Pem {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(f, "-----BEGIN {}-----", self.label)?;
+        if !self.contents.is_empty() {
+            writeln!(f, "{}", Base64Config::PEM.encode(&self.contents))?;
+        }
+        writeln!(f, "-----END {}-----", self.label)
+    }
+}
+
+impl Pem {
+    ///
+    /// `PemError::InvalidLabel` at the first byte of `label` out of printable ascii, starting `-----`, or a trailing `-`,
+    /// any of them breaks the begin and end lines
+    ///
+    pub fn new(label: &str, contents: &[u8]) -> Result<Self, PemError> {
+        let bytes = label.as_bytes();
+        for (i, c) in bytes.iter().enumerate() {
+            if !(b' '..=b'~').contains(c) || bytes[i..].starts_with(b"-----") || (b'-' == *c && i + 1 == bytes.len()) {
+                return Err(PemError::InvalidLabel(i));
+            }
+        }
+
+        Ok(Self { label: label.into(), contents: contents.into() })
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn contents(&self) -> &[u8] {
+        &self.contents
+    }
+
+    ///
+    /// decode the first armored block, the text before and after it is ignored,
+    /// the contents are decoded in constant time, as they are the private keys mostly
+    ///
+    pub fn decode(text: &str) -> Result<Self, PemError> {
+        let begin = text.find("-----BEGIN ").ok_or(PemError::MissingBegin)? + 11;
+        let label_len = text[begin..].find("-----").ok_or(PemError::MissingBegin)?;
+        let label = &text[begin..begin + label_len];
+        if label.contains(['\r', '\n']) {
+            return Err(PemError::MissingBegin);
+        }
+
+        let body = begin + label_len + 5;
+        let end = text[body..].find(&format!("-----END {}-----", label)).ok_or(PemError::MissingEnd)?;
+
+        let contents = Base64Config::PEM.constant_time(true).decode(&text[body..body + end]).map_err(|e| PemError::Decode(e.map_offset(|o| o + body)))?;
+
+        Ok(Self { label: label.into(), contents })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PemError {
+    /// the offset of the byte in the label given to `Pem::new`
+    InvalidLabel(usize),
+    MissingBegin,
+    MissingEnd,
+    /// the offset is counted from the start of the whole text
+    Decode(DecodeError),
+}
+
+impl Display for PemError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PemError::InvalidLabel(offset) => write!(f, "invalid pem label at offset {}", offset),
+            PemError::MissingBegin => write!(f, "missing pem begin line"),
+            PemError::MissingEnd => write!(f, "missing pem end line"),
+            PemError::Decode(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for PemError {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let contents: Vec<u8> = (0..100).collect();
+        let pem = Pem::new("JUXT BASEX", &contents).unwrap();
+
+        #[rustfmt::skip]
+        let expected = concat!(
+            "-----BEGIN JUXT BASEX-----\n",
+            "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8gISIjJCUmJygpKissLS4v\n",
+            "MDEyMzQ1Njc4OTo7PD0+P0BBQkNERUZHSElKS0xNTk9QUVJTVFVWV1hZWltcXV5f\n",
+            "YGFiYw==\n",
+            "-----END JUXT BASEX-----\n",
+        );
+        assert_eq!(pem.to_string(), expected);
+        assert_eq!(Pem::decode(expected), Ok(pem.clone()));
+        assert_eq!(Pem::decode(&format!("subject=juxt\r\n{}", expected.replace('\n', "\r\n"))), Ok(pem));
+
+        let empty = Pem::new("EMPTY", b"").unwrap();
+        assert_eq!(empty.to_string(), "-----BEGIN EMPTY-----\n-----END EMPTY-----\n");
+        assert_eq!(Pem::decode(&empty.to_string()).unwrap().contents(), b"");
+        assert_eq!(Pem::decode(&empty.to_string()).unwrap().label(), "EMPTY");
+
+        assert_eq!(Pem::decode("juxt"), Err(PemError::MissingBegin));
+        assert_eq!(Pem::decode("-----BEGIN X-----\nZg==\n-----END Y-----\n"), Err(PemError::MissingEnd));
+        assert_eq!(Pem::decode("-----BEGIN X-----\nZm*v\n-----END X-----\n"), Err(PemError::Decode(DecodeError::InvalidSymbol(20, b'*'))));
+
+        assert_eq!(Pem::new("", b"").unwrap().to_string(), "-----BEGIN -----\n-----END -----\n");
+        assert_eq!(Pem::new("X509 CRL-2", b"").unwrap().label(), "X509 CRL-2");
+        assert_eq!(Pem::new("JUXT\nBASEX", b""), Err(PemError::InvalidLabel(4)));
+        assert_eq!(Pem::new("JUXT \u{e9}", b""), Err(PemError::InvalidLabel(5)));
+        assert_eq!(Pem::new("JUXT-----BASEX", b""), Err(PemError::InvalidLabel(4)));
+        assert_eq!(Pem::new("JUXT-", b""), Err(PemError::InvalidLabel(4)));
+        assert_eq!(PemError::InvalidLabel(4).to_string(), "invalid pem label at offset 4");
+    }
+}
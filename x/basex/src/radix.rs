@@ -0,0 +1,169 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::AlphabetError;
+use crate::DecodeError;
+
+///
+/// big number radix conversion over a custom alphabet of 2..=256 unique symbols,
+/// every leading zero byte maps to one leading zero symbol and back, as base58
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BaseX {
+    alphabet: Vec<char>,
+    reverse: BTreeMap<char, u8>,
+}
+
+impl BaseX {
+    ///
+    /// the symbols are chars, the whitespace and the control chars are not allowed
+    ///
+    pub fn new(alphabet: &str) -> Result<Self, AlphabetError> {
+        let symbols: Vec<char> = alphabet.chars().collect();
+        if !(2..=256).contains(&symbols.len()) {
+            return Err(AlphabetError::InvalidLength(symbols.len()));
+        }
+
+        let mut reverse = BTreeMap::new();
+        for (i, c) in symbols.iter().enumerate() {
+            if c.is_whitespace() || c.is_control() {
+                return Err(AlphabetError::InvalidSymbol(i, *c));
+            }
+            if reverse.insert(*c, i as u8).is_some() {
+                return Err(AlphabetError::DuplicateSymbol(i, *c));
+            }
+        }
+
+        Ok(Self { alphabet: symbols, reverse })
+    }
+
+    pub fn radix(&self) -> usize {
+        self.alphabet.len()
+    }
+
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        String::from_iter(imp::to_radix(bytes, self.radix() as u32).iter().map(|d| self.alphabet[*d as usize]))
+    }
+
+    ///
+    /// the `DecodeError::InvalidSymbol` carries the byte offset and the first byte of the offending char
+    ///
+    pub fn decode(&self, text: &str) -> Result<Vec<u8>, DecodeError> {
+        let mut digits = Vec::with_capacity(text.len());
+        for (offset, c) in text.char_indices() {
+            match self.reverse.get(&c) {
+                Some(d) => digits.push(*d),
+                None => return Err(DecodeError::InvalidSymbol(offset, text.as_bytes()[offset])),
+            }
+        }
+        Ok(imp::from_radix(&digits, self.radix() as u32))
+    }
+}
+
+pub(crate) mod imp {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    ///
+    /// digits of `radix`, the most significant first
+    ///
+    pub fn to_radix(bytes: &[u8], radix: u32) -> Vec<u8> {
+        let zeros = bytes.iter().take_while(|b| 0 == **b).count();
+
+        let mut digits = Vec::with_capacity((bytes.len() - zeros) * 8 / (radix.ilog2() as usize) + 1);
+        for b in &bytes[zeros..] {
+            let mut carry = *b as u32;
+            for d in digits.iter_mut() {
+                carry += (*d as u32) << 8;
+                *d = (carry % radix) as u8;
+                carry /= radix;
+            }
+            while 0 < carry {
+                digits.push((carry % radix) as u8);
+                carry /= radix;
+            }
+        }
+
+        let mut result = vec![0; zeros];
+        result.extend(digits.iter().rev());
+        result
+    }
+
+    ///
+    /// the digits must be less than `radix`
+    ///
+    pub fn from_radix(digits: &[u8], radix: u32) -> Vec<u8> {
+        let zeros = digits.iter().take_while(|d| 0 == **d).count();
+
+        let mut bytes = Vec::with_capacity((digits.len() - zeros) * (radix.ilog2() as usize + 1) / 8 + 1);
+        for d in &digits[zeros..] {
+            let mut carry = *d as u32;
+            for b in bytes.iter_mut() {
+                carry += *b as u32 * radix;
+                *b = carry as u8;
+                carry >>= 8;
+            }
+            while 0 < carry {
+                bytes.push(carry as u8);
+                carry >>= 8;
+            }
+        }
+
+        let mut result = vec![0; zeros];
+        result.extend(bytes.iter().rev());
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Base58;
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let base2 = BaseX::new("01").unwrap();
+        let base10 = BaseX::new("0123456789").unwrap();
+        let base36 = BaseX::new("0123456789abcdefghijklmnopqrstuvwxyz").unwrap();
+        let base62 = BaseX::new("0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz").unwrap();
+        let base58 = BaseX::new("123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz").unwrap();
+        let base256 = BaseX::new(&String::from_iter((0x100..0x200).map(|c| char::from_u32(c).unwrap()))).unwrap();
+        assert_eq!(base256.radix(), 256);
+
+        #[rustfmt::skip]
+        let cases: [(&[u8], &str, &str, &str); 3] = [
+            (b"", "", "", ""),
+            (b"\x00\x00\x28\x7f\xb4\xcd", "00679457997", "00b8j559", "00jyw3x"),
+            (b"Hello World!", "22405534230753928650781647905", "2678lx5gvmsv1dro9b5", "T8dgcjRGkZ3aysdN"),
+        ];
+        for (bytes, b10, b36, b62) in cases {
+            assert_eq!(base10.encode(bytes), b10);
+            assert_eq!(base36.encode(bytes), b36);
+            assert_eq!(base62.encode(bytes), b62);
+            assert_eq!(base10.decode(b10).unwrap(), bytes);
+            assert_eq!(base36.decode(b36).unwrap(), bytes);
+            assert_eq!(base62.decode(b62).unwrap(), bytes);
+        }
+        assert_eq!(base2.encode(b"\x00\x05"), "0101");
+
+        let bytes: Vec<u8> = (0..=255).rev().chain(0..=255).collect();
+        for len in (0..bytes.len()).step_by(7) {
+            let bytes = &bytes[bytes.len() - len..];
+            assert_eq!(base58.encode(bytes), Base58::BITCOIN.encode(bytes));
+            for base in [&base2, &base36, &base256] {
+                assert_eq!(base.decode(&base.encode(bytes)).unwrap(), bytes);
+            }
+        }
+
+        assert_eq!(base36.decode("0az!"), Err(DecodeError::InvalidSymbol(3, b'!')));
+        assert_eq!(base256.decode("ĀāX"), Err(DecodeError::InvalidSymbol(4, b'X')));
+        assert_eq!(base36.decode("0A"), Err(DecodeError::InvalidSymbol(1, b'A')));
+
+        assert_eq!(BaseX::new("0"), Err(AlphabetError::InvalidLength(1)));
+        assert_eq!(BaseX::new(&String::from_iter((0x100..0x201).map(|c| char::from_u32(c).unwrap()))), Err(AlphabetError::InvalidLength(257)));
+        assert_eq!(BaseX::new("0120"), Err(AlphabetError::DuplicateSymbol(3, '0')));
+        assert_eq!(BaseX::new("01 2"), Err(AlphabetError::InvalidSymbol(2, ' ')));
+    }
+}
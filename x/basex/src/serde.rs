@@ -0,0 +1,160 @@
+use ::serde::Deserialize;
+use ::serde::Deserializer;
+use ::serde::Serialize;
+use ::serde::Serializer;
+
+use crate::Base64;
+
+///
+/// `#[serde(with = "juxt_basex::serde::base64")]`, the standard alphabet, padded,
+/// the padding is optional on deserialize
+///
+pub mod base64 {
+    use alloc::vec::Vec;
+
+    use ::serde::Deserializer;
+    use ::serde::Serializer;
+
+    use crate::Base64;
+    use crate::Base64Config;
+
+    pub fn serialize<T: AsRef<[u8]>, S: Serializer>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&Base64Config::STANDARD.encode(bytes.as_ref()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        deserializer.deserialize_str(super::imp::TextVisitor("base64", Base64::decode))
+    }
+}
+
+///
+/// `#[serde(with = "juxt_basex::serde::base64_url_safe_no_pad")]`, the url and filename safe alphabet, no padding
+///
+pub mod base64_url_safe_no_pad {
+    use alloc::vec::Vec;
+
+    use ::serde::Deserializer;
+    use ::serde::Serializer;
+
+    use crate::Base64Config;
+
+    pub fn serialize<T: AsRef<[u8]>, S: Serializer>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&Base64Config::URL_SAFE_NO_PAD.encode(bytes.as_ref()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        deserializer.deserialize_str(super::imp::TextVisitor("url safe base64", |text: &str| Base64Config::URL_SAFE_NO_PAD.decode(text)))
+    }
+}
+
+///
+/// `#[serde(with = "juxt_basex::serde::hex")]`, lowercase, both cases on deserialize
+///
+pub mod hex {
+    use alloc::vec::Vec;
+
+    use ::serde::Deserializer;
+    use ::serde::Serializer;
+
+    use crate::Base16;
+
+    pub fn serialize<T: AsRef<[u8]>, S: Serializer>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&Base16::from(bytes.as_ref()).to_string_lower())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        deserializer.deserialize_str(super::imp::TextVisitor("hex", Base16::decode))
+    }
+}
+
+///
+/// as the text of the standard alphabet, padded
+///
+impl Serialize for Base64 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+///
+/// from the text of the standard alphabet, the padding is optional
+///
+impl<'de> Deserialize<'de> for Base64 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserializer.deserialize_str(imp::TextVisitor("base64", Base64::decode))?;
+        Ok(Self::from(bytes.as_slice()))
+    }
+}
+
+mod imp {
+    use alloc::vec::Vec;
+    use core::fmt::Formatter;
+
+    use ::serde::de::Error;
+    use ::serde::de::Visitor;
+
+    use crate::DecodeError;
+
+    ///
+    /// the name of the encoding for the error message, the decode function
+    ///
+    pub struct TextVisitor<F>(pub &'static str, pub F);
+
+    impl<F: FnOnce(&str) -> Result<Vec<u8>, DecodeError>> Visitor<'_> for TextVisitor<F> {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+            write!(formatter, "a {} string", self.0)
+        }
+
+        fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+            (self.1)(v).map_err(E::custom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    use ::serde::Deserialize;
+    use ::serde::Serialize;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Config {
+        #[serde(with = "crate::serde::base64")]
+        key: Vec<u8>,
+        #[serde(with = "crate::serde::base64_url_safe_no_pad")]
+        token: Vec<u8>,
+        #[serde(with = "crate::serde::hex")]
+        digest: Vec<u8>,
+    }
+
+    #[test]
+    fn it_works() {
+        let config = Config {
+            key: b"juxt".to_vec(),
+            token: b"\xfb\xff".to_vec(),
+            digest: b"\x0a\xbc".to_vec(),
+        };
+        let json = r#"{"key":"anV4dA==","token":"-_8","digest":"0abc"}"#;
+        assert_eq!(serde_json::to_string(&config).unwrap(), json);
+        assert_eq!(serde_json::from_str::<Config>(json).unwrap(), config);
+        assert_eq!(serde_json::from_str::<Config>(r#"{"key":"anV4dA","token":"-_8","digest":"0ABC"}"#).unwrap(), config);
+
+        let e = serde_json::from_str::<Config>(r#"{"key":"anV4d!==","token":"-_8","digest":"0abc"}"#).unwrap_err();
+        assert!(e.to_string().starts_with("invalid symbol"), "{}", e);
+        assert!(serde_json::from_str::<Config>(r#"{"key":"anV4dA==","token":"-_8=","digest":"0abc"}"#).is_err());
+        assert!(serde_json::from_str::<Config>(r#"{"key":"anV4dA==","token":"-_8","digest":"0ab"}"#).is_err());
+        assert!(serde_json::from_str::<Config>(r#"{"key":0,"token":"-_8","digest":"0abc"}"#).unwrap_err().to_string().contains("a base64 string"));
+
+        let base64 = Base64::from(b"juxt_basex".as_slice());
+        assert_eq!(serde_json::to_string(&base64).unwrap(), "\"anV4dF9iYXNleA==\"");
+        let base64: Base64 = serde_json::from_str("\"anV4dF9iYXNleA\"").unwrap();
+        assert_eq!(base64.to_string(), String::from("anV4dF9iYXNleA=="));
+    }
+}
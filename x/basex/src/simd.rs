@@ -0,0 +1,297 @@
+//! the fast paths of base64, each one handles the leading full blocks only and returns `(read, written)`,
+//! the rest, and any block with an invalid symbol, is left to the scalar code, which reports the error
+
+///
+/// encode as many full blocks of 3 bytes as possible
+///
+pub fn encode(bytes: &[u8], alphabet: &[u8; 65], out: &mut [u8]) -> (usize, usize) {
+    #[allow(unused_mut)]
+    let (mut read, mut written) = (0, 0);
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if x86::has_avx2() {
+            (read, written) = unsafe { x86::encode_avx2(bytes, alphabet, out) };
+        } else if x86::has_ssse3() {
+            (read, written) = unsafe { x86::encode_ssse3(bytes, alphabet, out) };
+        }
+    }
+
+    let (r, w) = swar::encode(&bytes[read..], alphabet, &mut out[written..]);
+    (read + r, written + w)
+}
+
+///
+/// decode as many full blocks of 4 valid symbols as possible, `text` is without the padding
+///
+pub fn decode(text: &[u8], alphabet: &[u8; 65], reverse: &[u8; 256], out: &mut [u8]) -> (usize, usize) {
+    #[allow(unused_mut)]
+    let (mut read, mut written) = (0, 0);
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if x86::has_avx2() {
+            (read, written) = unsafe { x86::decode_avx2(text, alphabet, out) };
+        } else if x86::has_ssse3() {
+            (read, written) = unsafe { x86::decode_ssse3(text, alphabet, out) };
+        }
+    }
+
+    let (r, w) = swar::decode(&text[read..], reverse, &mut out[written..]);
+    (read + r, written + w)
+}
+
+///
+/// 6 bytes <-> 8 symbols through a `u64`, portable
+///
+pub mod swar {
+    pub fn encode(bytes: &[u8], alphabet: &[u8; 65], out: &mut [u8]) -> (usize, usize) {
+        let mut read = 0;
+        let mut written = 0;
+
+        // load 8 bytes, use the first 6
+        while read + 8 <= bytes.len() && written + 8 <= out.len() {
+            let v = u64::from_be_bytes(bytes[read..read + 8].try_into().unwrap());
+            for (i, o) in out[written..written + 8].iter_mut().enumerate() {
+                *o = alphabet[(v >> (58 - i * 6)) as usize & 0b00111111];
+            }
+            read += 6;
+            written += 8;
+        }
+
+        (read, written)
+    }
+
+    pub fn decode(text: &[u8], reverse: &[u8; 256], out: &mut [u8]) -> (usize, usize) {
+        let mut read = 0;
+        let mut written = 0;
+
+        while read + 8 <= text.len() && written + 6 <= out.len() {
+            let mut v = 0u64;
+            let mut invalid = 0x00;
+            for c in &text[read..read + 8] {
+                let index = reverse[*c as usize];
+                invalid |= index;
+                v = (v << 6) | index as u64;
+            }
+            // 0xff for the invalid symbols, the valid ones are less than 64
+            if 0 != invalid & 0x80 {
+                break;
+            }
+            out[written..written + 6].copy_from_slice(&v.to_be_bytes()[2..]);
+            read += 8;
+            written += 6;
+        }
+
+        (read, written)
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub mod x86 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    #[cfg(feature = "std")]
+    pub fn has_avx2() -> bool {
+        std::is_x86_feature_detected!("avx2")
+    }
+
+    #[cfg(feature = "std")]
+    pub fn has_ssse3() -> bool {
+        std::is_x86_feature_detected!("ssse3")
+    }
+
+    ///
+    /// no runtime detection without std, fall back to the target features of the build
+    ///
+    #[cfg(not(feature = "std"))]
+    pub fn has_avx2() -> bool {
+        cfg!(target_feature = "avx2")
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub fn has_ssse3() -> bool {
+        cfg!(target_feature = "ssse3")
+    }
+
+    ///
+    /// the fast decode knows the symbols `A-Za-z0-9` by their ranges, the other 2 ones by comparison
+    ///
+    fn is_decodable(alphabet: &[u8; 65]) -> bool {
+        alphabet[..62] == crate::imp::BASE64_ALPHABET[..62]
+    }
+
+    ///
+    /// 12 bytes -> 16 symbols per round, read 16 bytes
+    ///
+    #[target_feature(enable = "ssse3")]
+    pub unsafe fn encode_ssse3(bytes: &[u8], alphabet: &[u8; 65], out: &mut [u8]) -> (usize, usize) {
+        let tables = [0, 16, 32, 48].map(|i| _mm_loadu_si128(alphabet[i..].as_ptr() as *const __m128i));
+        let spread = _mm_setr_epi8(1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10);
+
+        let mut read = 0;
+        let mut written = 0;
+        while read + 16 <= bytes.len() && written + 16 <= out.len() {
+            let v = _mm_shuffle_epi8(_mm_loadu_si128(bytes.as_ptr().add(read) as *const __m128i), spread);
+
+            // the 4 indices of 6 bits out of [b1, b0, b2, b1] in each 32 bits lane
+            let hi = _mm_mulhi_epu16(_mm_and_si128(v, _mm_set1_epi32(0x0fc0fc00)), _mm_set1_epi32(0x04000040));
+            let lo = _mm_mullo_epi16(_mm_and_si128(v, _mm_set1_epi32(0x003f03f0)), _mm_set1_epi32(0x01000010));
+            let indices = _mm_or_si128(hi, lo);
+
+            // the alphabet in 4 tables of 16 symbols, picked by the top 2 bits of the index
+            let nibble = _mm_and_si128(indices, _mm_set1_epi8(0x0f));
+            let table = _mm_and_si128(_mm_srli_epi16(indices, 4), _mm_set1_epi8(0x03));
+            let mut symbols = _mm_setzero_si128();
+            for (k, t) in tables.iter().enumerate() {
+                let mask = _mm_cmpeq_epi8(table, _mm_set1_epi8(k as i8));
+                symbols = _mm_or_si128(symbols, _mm_and_si128(mask, _mm_shuffle_epi8(*t, nibble)));
+            }
+
+            _mm_storeu_si128(out.as_mut_ptr().add(written) as *mut __m128i, symbols);
+            read += 12;
+            written += 16;
+        }
+
+        (read, written)
+    }
+
+    ///
+    /// 24 bytes -> 32 symbols per round, read 28 bytes
+    ///
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn encode_avx2(bytes: &[u8], alphabet: &[u8; 65], out: &mut [u8]) -> (usize, usize) {
+        let tables = [0, 16, 32, 48].map(|i| _mm256_broadcastsi128_si256(_mm_loadu_si128(alphabet[i..].as_ptr() as *const __m128i)));
+        let spread = _mm256_setr_epi8(1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10, 1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10);
+
+        let mut read = 0;
+        let mut written = 0;
+        while read + 28 <= bytes.len() && written + 32 <= out.len() {
+            let lo = _mm_loadu_si128(bytes.as_ptr().add(read) as *const __m128i);
+            let hi = _mm_loadu_si128(bytes.as_ptr().add(read + 12) as *const __m128i);
+            let v = _mm256_shuffle_epi8(_mm256_inserti128_si256(_mm256_castsi128_si256(lo), hi, 1), spread);
+
+            let hi = _mm256_mulhi_epu16(_mm256_and_si256(v, _mm256_set1_epi32(0x0fc0fc00)), _mm256_set1_epi32(0x04000040));
+            let lo = _mm256_mullo_epi16(_mm256_and_si256(v, _mm256_set1_epi32(0x003f03f0)), _mm256_set1_epi32(0x01000010));
+            let indices = _mm256_or_si256(hi, lo);
+
+            let nibble = _mm256_and_si256(indices, _mm256_set1_epi8(0x0f));
+            let table = _mm256_and_si256(_mm256_srli_epi16(indices, 4), _mm256_set1_epi8(0x03));
+            let mut symbols = _mm256_setzero_si256();
+            for (k, t) in tables.iter().enumerate() {
+                let mask = _mm256_cmpeq_epi8(table, _mm256_set1_epi8(k as i8));
+                symbols = _mm256_or_si256(symbols, _mm256_and_si256(mask, _mm256_shuffle_epi8(*t, nibble)));
+            }
+
+            _mm256_storeu_si256(out.as_mut_ptr().add(written) as *mut __m256i, symbols);
+            read += 24;
+            written += 32;
+        }
+
+        (read, written)
+    }
+
+    ///
+    /// 16 symbols -> 12 bytes per round, write exactly 12 bytes
+    ///
+    #[target_feature(enable = "ssse3")]
+    pub unsafe fn decode_ssse3(text: &[u8], alphabet: &[u8; 65], out: &mut [u8]) -> (usize, usize) {
+        if !is_decodable(alphabet) {
+            return (0, 0);
+        }
+
+        let s62 = _mm_set1_epi8(alphabet[62] as i8);
+        let s63 = _mm_set1_epi8(alphabet[63] as i8);
+        let pack = _mm_setr_epi8(2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1);
+
+        let mut read = 0;
+        let mut written = 0;
+        while read + 16 <= text.len() && written + 12 <= out.len() {
+            let c = _mm_loadu_si128(text.as_ptr().add(read) as *const __m128i);
+
+            // signed compare, the bytes above 0x7f fall in no range
+            let upper = _mm_and_si128(_mm_cmpgt_epi8(c, _mm_set1_epi8(b'A' as i8 - 1)), _mm_cmpgt_epi8(_mm_set1_epi8(b'Z' as i8 + 1), c));
+            let lower = _mm_and_si128(_mm_cmpgt_epi8(c, _mm_set1_epi8(b'a' as i8 - 1)), _mm_cmpgt_epi8(_mm_set1_epi8(b'z' as i8 + 1), c));
+            let digit = _mm_and_si128(_mm_cmpgt_epi8(c, _mm_set1_epi8(b'0' as i8 - 1)), _mm_cmpgt_epi8(_mm_set1_epi8(b'9' as i8 + 1), c));
+            let is62 = _mm_cmpeq_epi8(c, s62);
+            let is63 = _mm_cmpeq_epi8(c, s63);
+
+            let valid = _mm_or_si128(_mm_or_si128(_mm_or_si128(upper, lower), _mm_or_si128(digit, is62)), is63);
+            if 0xffff != _mm_movemask_epi8(valid) {
+                break;
+            }
+
+            let mut shift = _mm_and_si128(upper, _mm_set1_epi8(-65));
+            shift = _mm_or_si128(shift, _mm_and_si128(lower, _mm_set1_epi8(-71)));
+            shift = _mm_or_si128(shift, _mm_and_si128(digit, _mm_set1_epi8(4)));
+            shift = _mm_or_si128(shift, _mm_and_si128(is62, _mm_sub_epi8(_mm_set1_epi8(62), c)));
+            shift = _mm_or_si128(shift, _mm_and_si128(is63, _mm_sub_epi8(_mm_set1_epi8(63), c)));
+            let indices = _mm_add_epi8(c, shift);
+
+            // [a, b, c, d] -> a << 18 | b << 12 | c << 6 | d in each 32 bits lane, then big endian 3 bytes
+            let v = _mm_madd_epi16(_mm_maddubs_epi16(indices, _mm_set1_epi32(0x01400140)), _mm_set1_epi32(0x00011000));
+            let v = _mm_shuffle_epi8(v, pack);
+
+            let dst = out.as_mut_ptr().add(written);
+            _mm_storel_epi64(dst as *mut __m128i, v);
+            core::ptr::write_unaligned(dst.add(8) as *mut i32, _mm_cvtsi128_si32(_mm_srli_si128(v, 8)));
+            read += 16;
+            written += 12;
+        }
+
+        (read, written)
+    }
+
+    ///
+    /// 32 symbols -> 24 bytes per round, write exactly 24 bytes
+    ///
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn decode_avx2(text: &[u8], alphabet: &[u8; 65], out: &mut [u8]) -> (usize, usize) {
+        if !is_decodable(alphabet) {
+            return (0, 0);
+        }
+
+        let s62 = _mm256_set1_epi8(alphabet[62] as i8);
+        let s63 = _mm256_set1_epi8(alphabet[63] as i8);
+        let pack = _mm256_setr_epi8(2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1, 2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1);
+        let compact = _mm256_setr_epi32(0, 1, 2, 4, 5, 6, 3, 7);
+
+        let mut read = 0;
+        let mut written = 0;
+        while read + 32 <= text.len() && written + 24 <= out.len() {
+            let c = _mm256_loadu_si256(text.as_ptr().add(read) as *const __m256i);
+
+            let upper = _mm256_and_si256(_mm256_cmpgt_epi8(c, _mm256_set1_epi8(b'A' as i8 - 1)), _mm256_cmpgt_epi8(_mm256_set1_epi8(b'Z' as i8 + 1), c));
+            let lower = _mm256_and_si256(_mm256_cmpgt_epi8(c, _mm256_set1_epi8(b'a' as i8 - 1)), _mm256_cmpgt_epi8(_mm256_set1_epi8(b'z' as i8 + 1), c));
+            let digit = _mm256_and_si256(_mm256_cmpgt_epi8(c, _mm256_set1_epi8(b'0' as i8 - 1)), _mm256_cmpgt_epi8(_mm256_set1_epi8(b'9' as i8 + 1), c));
+            let is62 = _mm256_cmpeq_epi8(c, s62);
+            let is63 = _mm256_cmpeq_epi8(c, s63);
+
+            let valid = _mm256_or_si256(_mm256_or_si256(_mm256_or_si256(upper, lower), _mm256_or_si256(digit, is62)), is63);
+            if -1 != _mm256_movemask_epi8(valid) {
+                break;
+            }
+
+            let mut shift = _mm256_and_si256(upper, _mm256_set1_epi8(-65));
+            shift = _mm256_or_si256(shift, _mm256_and_si256(lower, _mm256_set1_epi8(-71)));
+            shift = _mm256_or_si256(shift, _mm256_and_si256(digit, _mm256_set1_epi8(4)));
+            shift = _mm256_or_si256(shift, _mm256_and_si256(is62, _mm256_sub_epi8(_mm256_set1_epi8(62), c)));
+            shift = _mm256_or_si256(shift, _mm256_and_si256(is63, _mm256_sub_epi8(_mm256_set1_epi8(63), c)));
+            let indices = _mm256_add_epi8(c, shift);
+
+            let v = _mm256_madd_epi16(_mm256_maddubs_epi16(indices, _mm256_set1_epi32(0x01400140)), _mm256_set1_epi32(0x00011000));
+            let v = _mm256_permutevar8x32_epi32(_mm256_shuffle_epi8(v, pack), compact);
+
+            let dst = out.as_mut_ptr().add(written);
+            _mm_storeu_si128(dst as *mut __m128i, _mm256_castsi256_si128(v));
+            _mm_storel_epi64(dst.add(16) as *mut __m128i, _mm256_extracti128_si256(v, 1));
+            read += 32;
+            written += 24;
+        }
+
+        (read, written)
+    }
+}
@@ -0,0 +1,431 @@
+use std::io::Error;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Write;
+use std::vec::Vec;
+
+use crate::imp;
+use crate::Base64Config;
+use crate::DecodeError;
+use crate::Padding;
+
+///
+/// encode into `W` on the fly, call `finish` to write the last quantum,
+/// `write` takes at most 768 bytes at a time, once taken they are buffered,
+/// a failure of `W` after that is retried, and reported if it persists, by the next call
+///
+pub struct Base64Writer<W: Write> {
+    inner: W,
+    config: Base64Config,
+    pending: [u8; 3],
+    pending_len: usize,
+    column: usize,
+    /// the symbols and the line endings not written to `inner` yet, 1024 symbols and 256 line endings at most
+    out: [u8; 1536],
+    out_pos: usize,
+    out_len: usize,
+}
+
+impl<W: Write> Base64Writer<W> {
+    pub fn new(inner: W, config: Base64Config) -> Self {
+        Self {
+            inner,
+            config,
+            pending: [0x00; 3],
+            pending_len: 0,
+            column: 0,
+            out: [0x00; 1536],
+            out_pos: 0,
+            out_len: 0,
+        }
+    }
+
+    ///
+    /// write the 0-2 pending bytes with the padding, flush and return the inner writer
+    ///
+    pub fn finish(mut self) -> std::io::Result<W> {
+        self.write_out()?;
+
+        let mut symbols = [0x00; 4];
+        let n = imp::encode_to_slice(&self.pending[..self.pending_len], &self.config.alphabet, Padding::Never != self.config.padding, &mut symbols);
+        self.pending_len = 0;
+        self.emit(&symbols[..n]);
+        self.write_out()?;
+
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    ///
+    /// append to `out`, the line ending goes before the next symbol, so there is none after the last line
+    ///
+    fn emit(&mut self, mut symbols: &[u8]) {
+        let width = self.config.line_width;
+        while !symbols.is_empty() {
+            if 0 < width && width == self.column {
+                let ending = self.config.line_ending.as_bytes();
+                self.out[self.out_len..self.out_len + ending.len()].copy_from_slice(ending);
+                self.out_len += ending.len();
+                self.column = 0;
+            }
+
+            let n = if 0 == width { symbols.len() } else { symbols.len().min(width - self.column) };
+            self.out[self.out_len..self.out_len + n].copy_from_slice(&symbols[..n]);
+            self.out_len += n;
+            self.column += n;
+            symbols = &symbols[n..];
+        }
+    }
+
+    ///
+    /// write `out` to `inner`, keep the progress on error
+    ///
+    fn write_out(&mut self) -> std::io::Result<()> {
+        while self.out_pos < self.out_len {
+            match self.inner.write(&self.out[self.out_pos..self.out_len]) {
+                Ok(0) => return Err(Error::new(ErrorKind::WriteZero, "failed to write the buffered symbols")),
+                Ok(n) => self.out_pos += n,
+                Err(e) if ErrorKind::Interrupted == e.kind() => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        self.out_pos = 0;
+        self.out_len = 0;
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for Base64Writer<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        // nothing of `buf` is taken if the symbols of the last call still can not be written
+        self.write_out()?;
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let taken = buf.len().min(768 - self.pending_len);
+        let mut bytes = &buf[..taken];
+        let mut symbols = [0x00; 1024];
+        let mut n = 0;
+
+        if 0 < self.pending_len {
+            let m = bytes.len().min(3 - self.pending_len);
+            self.pending[self.pending_len..self.pending_len + m].copy_from_slice(&bytes[..m]);
+            self.pending_len += m;
+            bytes = &bytes[m..];
+
+            if 3 > self.pending_len {
+                return Ok(taken);
+            }
+
+            n += imp::encode_to_slice(&self.pending, &self.config.alphabet, false, &mut symbols);
+            self.pending_len = 0;
+        }
+
+        let full = bytes.len() / 3 * 3;
+        n += imp::encode_to_slice(&bytes[..full], &self.config.alphabet, false, &mut symbols[n..]);
+
+        let rest = &bytes[full..];
+        self.pending[..rest.len()].copy_from_slice(rest);
+        self.pending_len = rest.len();
+
+        self.emit(&symbols[..n]);
+        // `taken` bytes are in, the error is up to the next call
+        let _ = self.write_out();
+
+        Ok(taken)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.write_out()?;
+        self.inner.flush()
+    }
+}
+
+///
+/// decode from `R` on the fly, the `DecodeError` is wrapped as `ErrorKind::InvalidData`
+/// with the offset counted in raw bytes from the start of the stream, as `Base64Config::decode`
+///
+pub struct Base64Reader<R: Read> {
+    inner: R,
+    config: Base64Config,
+    symbols: [u8; 1024],
+    symbols_len: usize,
+    decoded: [u8; 768],
+    decoded_pos: usize,
+    decoded_len: usize,
+    offset: usize,
+    /// the raw bytes read from `inner`
+    raw_len: usize,
+    /// (symbol offset, raw offset) where the whitespace before a symbol shifts the raw offsets further
+    shifts: Vec<(usize, usize)>,
+    eof: bool,
+    padding: Option<usize>,
+}
+
+impl<R: Read> Base64Reader<R> {
+    pub fn new(inner: R, config: Base64Config) -> Self {
+        Self {
+            inner,
+            config,
+            symbols: [0x00; 1024],
+            symbols_len: 0,
+            decoded: [0x00; 768],
+            decoded_pos: 0,
+            decoded_len: 0,
+            offset: 0,
+            raw_len: 0,
+            shifts: Vec::new(),
+            eof: false,
+            padding: None,
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn decode(&mut self) -> Result<(), DecodeError> {
+        let mut len = if self.eof { self.symbols_len } else { self.symbols_len / 4 * 4 };
+        // the quantum with padding must be the last one
+        if !self.eof && 0 < len && b'=' == self.symbols[len - 1] {
+            len -= 4;
+        }
+
+        let config = &self.config;
+        let text = &self.symbols[..len];
+        let end = imp::validate(text, |c| config.lookup(c), config.padding)?;
+        self.decoded_len = config.decode_symbols(&text[..end], &mut self.decoded)?;
+        self.decoded_pos = 0;
+        if end < len {
+            self.padding = Some(self.raw(self.offset + end));
+        }
+
+        self.symbols.copy_within(len..self.symbols_len, 0);
+        self.symbols_len -= len;
+        self.offset += len;
+
+        // the last shift at or before `offset` still applies, the ones before it do not
+        let used = self.shifts.iter().take_while(|(symbol, _)| *symbol <= self.offset).count();
+        self.shifts.drain(..used.saturating_sub(1));
+
+        Ok(())
+    }
+
+    ///
+    /// the raw offset of the symbol at `offset`, or just after the last symbol at the end, as `imp::locate`
+    ///
+    fn raw(&self, offset: usize) -> usize {
+        self.shifts.iter().rev().find(|(symbol, _)| *symbol <= offset).map_or(offset, |(symbol, raw)| raw + (offset - symbol))
+    }
+
+    ///
+    /// move the non whitespace bytes of the `n` just read to the front, note where the raw offsets shift
+    ///
+    fn compact(&mut self, n: usize) -> usize {
+        let start = self.symbols_len;
+        let mut len = start;
+        for i in start..start + n {
+            if self.symbols[i].is_ascii_whitespace() {
+                continue;
+            }
+
+            let symbol = self.offset + len;
+            let raw = self.raw_len + (i - start);
+            if raw != self.raw(symbol) {
+                self.shifts.push((symbol, raw));
+            }
+            self.symbols[len] = self.symbols[i];
+            len += 1;
+        }
+        len - start
+    }
+}
+
+impl<R: Read> Read for Base64Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            if self.decoded_pos < self.decoded_len {
+                let n = buf.len().min(self.decoded_len - self.decoded_pos);
+                buf[..n].copy_from_slice(&self.decoded[self.decoded_pos..self.decoded_pos + n]);
+                self.decoded_pos += n;
+                return Ok(n);
+            }
+
+            if self.eof && 0 == self.symbols_len {
+                return Ok(0);
+            }
+
+            if !self.eof {
+                match self.inner.read(&mut self.symbols[self.symbols_len..]) {
+                    Ok(0) => self.eof = true,
+                    Ok(n) => {
+                        self.symbols_len += if self.config.ignore_whitespace { self.compact(n) } else { n };
+                        self.raw_len += n;
+                    }
+                    Err(e) if ErrorKind::Interrupted == e.kind() => continue,
+                    Err(e) => return Err(e),
+                }
+            }
+
+            // nothing may follow the quantum with padding
+            if let Some(padding) = self.padding {
+                if 0 < self.symbols_len {
+                    return Err(Error::new(ErrorKind::InvalidData, DecodeError::InvalidPadding(padding)));
+                }
+            }
+
+            let offset = self.offset;
+            self.decode().map_err(|e| Error::new(ErrorKind::InvalidData, e.map_offset(|o| self.raw(o + offset))))?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::copy;
+    use std::vec::Vec;
+
+    use crate::Base64;
+
+    use super::*;
+
+    ///
+    /// hand out at most `step` bytes per read
+    ///
+    struct Trickle<'a>(&'a [u8], usize);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = buf.len().min(self.1).min(self.0.len());
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    ///
+    /// fail every third call, write at most 5 bytes otherwise
+    ///
+    struct Flaky {
+        out: Vec<u8>,
+        calls: usize,
+        failing: bool,
+    }
+
+    impl Write for Flaky {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.calls += 1;
+            if self.failing && self.calls.is_multiple_of(3) {
+                return Err(Error::other("flaky"));
+            }
+            let n = buf.len().min(5);
+            self.out.extend_from_slice(&buf[..n]);
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn it_works() {
+        let bytes: Vec<u8> = (0..=255).cycle().take(3000).collect();
+
+        for config in [Base64Config::STANDARD, Base64Config::URL_SAFE_NO_PAD] {
+            for len in [0, 1, 2, 3, 4, 767, 768, 769, 3000] {
+                let expected = config.encode(&bytes[..len]);
+
+                for step in [1, 2, 5, 1000] {
+                    let mut writer = Base64Writer::new(Vec::new(), config);
+                    bytes[..len].chunks(step).for_each(|chunk| writer.write_all(chunk).unwrap());
+                    assert_eq!(writer.finish().unwrap(), expected.as_bytes());
+
+                    let mut reader = Base64Reader::new(Trickle(expected.as_bytes(), step), config);
+                    let mut out = Vec::new();
+                    let mut buf = [0x00; 7];
+                    loop {
+                        let n = reader.read(&mut buf).unwrap();
+                        if 0 == n {
+                            break;
+                        }
+                        out.extend_from_slice(&buf[..n]);
+                    }
+                    assert_eq!(out, &bytes[..len]);
+                }
+
+                let mut out = Vec::new();
+                copy(&mut Base64Reader::new(expected.as_bytes(), config), &mut out).unwrap();
+                assert_eq!(out, &bytes[..len]);
+            }
+        }
+
+        let text = std::format!("{}*AAA", "A".repeat(2000));
+        let e = copy(&mut Base64Reader::new(Trickle(text.as_bytes(), 3), Base64Config::STANDARD), &mut std::io::sink()).unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, e.kind());
+        assert_eq!(e.into_inner().unwrap().downcast::<DecodeError>().unwrap(), std::boxed::Box::new(DecodeError::InvalidSymbol(2000, b'*')));
+
+        for text in [&b"Zg==Zm9v"[..], b"Zg==Zg==", b"Zm9vYg==Zg"] {
+            let expected = Base64::decode(core::str::from_utf8(text).unwrap()).unwrap_err();
+            for step in [1, 2, 5, 1000] {
+                let e = copy(&mut Base64Reader::new(Trickle(text, step), Base64Config::STANDARD), &mut std::io::sink()).unwrap_err();
+                assert_eq!(e.into_inner().unwrap().downcast::<DecodeError>().unwrap(), std::boxed::Box::new(expected));
+            }
+        }
+
+        let bytes: Vec<u8> = (0..=255).cycle().take(200).collect();
+        let expected = Base64Config::MIME.encode(&bytes);
+        for step in [1, 3, 77, 1000] {
+            let mut writer = Base64Writer::new(Vec::new(), Base64Config::MIME);
+            bytes.chunks(step).for_each(|chunk| writer.write_all(chunk).unwrap());
+            assert_eq!(writer.finish().unwrap(), expected.as_bytes());
+
+            let mut out = Vec::new();
+            copy(&mut Base64Reader::new(Trickle(expected.as_bytes(), step), Base64Config::MIME), &mut out).unwrap();
+            assert_eq!(out, bytes);
+        }
+
+        let e = copy(&mut Base64Reader::new(&b"Zm9vYg"[..], Base64Config::STANDARD), &mut std::io::sink()).unwrap_err();
+        assert_eq!(e.into_inner().unwrap().downcast::<DecodeError>().unwrap(), std::boxed::Box::new(DecodeError::InvalidPadding(6)));
+    }
+
+    #[test]
+    fn write_resumes_after_error() {
+        let bytes: Vec<u8> = (0..=255).cycle().take(2000).collect();
+        for config in [Base64Config::STANDARD, Base64Config::MIME] {
+            for step in [1, 4, 1000] {
+                let mut writer = Base64Writer::new(Flaky { out: Vec::new(), calls: 0, failing: true }, config);
+                for chunk in bytes.chunks(step) {
+                    let mut chunk = chunk;
+                    while !chunk.is_empty() {
+                        // an error takes nothing, the same bytes go again
+                        if let Ok(n) = writer.write(chunk) {
+                            chunk = &chunk[n..];
+                        }
+                    }
+                }
+                writer.inner.failing = false;
+                assert_eq!(writer.finish().unwrap().out, config.encode(&bytes).as_bytes());
+            }
+        }
+    }
+
+    #[test]
+    fn read_offsets_as_decode() {
+        let config = Base64Config::MIME;
+        let texts: [&[u8]; 4] = [b"Zm9v\r\n  Ym*y", b" Zm9v\n\nZg==\r\nZg==", b"Zm9v\nYg \n", b"\tZm9vYmFy\r\n\r\nZm9vYmFy\r\nZm9vYm\x01y\r\n"];
+        for text in texts {
+            let expected = config.decode(core::str::from_utf8(text).unwrap()).unwrap_err();
+            for step in [1, 2, 5, 1000] {
+                let e = copy(&mut Base64Reader::new(Trickle(text, step), config), &mut std::io::sink()).unwrap_err();
+                assert_eq!(e.into_inner().unwrap().downcast::<DecodeError>().unwrap(), std::boxed::Box::new(expected));
+            }
+        }
+
+        let text = std::format!("{}\n*", "QUJD\n".repeat(500));
+        let e = copy(&mut Base64Reader::new(Trickle(text.as_bytes(), 7), config), &mut std::io::sink()).unwrap_err();
+        assert_eq!(e.into_inner().unwrap().downcast::<DecodeError>().unwrap(), std::boxed::Box::new(DecodeError::InvalidSymbol(2501, b'*')));
+    }
+}
@@ -1,6 +1,11 @@
 use std::error::Error;
 use std::fmt::Debug;
 use std::fmt::Display;
+use std::hash::BuildHasherDefault;
+use std::hash::Hasher;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Write;
 use std::str::FromStr;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -15,15 +20,104 @@ impl Display for Md5 {
     }
 }
 
+///
+/// hashes `s`, it does **not** parse a hex digest
+///
+/// deprecated: this will parse a hex digest like `Md5::from_hex` from 0.2.0 on,
+/// use `Md5::hash_str` or `Md5::from_hex` to make the intent explicit,
+/// `Md5::from_str` warns through the deprecated inherent shim, `str::parse` can not
+///
 impl FromStr for Md5 {
     type Err = Box<dyn Error>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self::from_vec(&mut s.as_bytes().into()))
+        Ok(Self::hash_str(s))
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// hex digest must be 32 chars
+    InvalidLength(usize),
+    /// non-hex char at the index
+    InvalidChar(usize),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidLength(len) => write!(f, "invalid md5 hex length {}, expected 32", len),
+            ParseError::InvalidChar(index) => write!(f, "invalid md5 hex char at index {}", index),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
 impl Md5 {
+    ///
+    /// shadow `FromStr::from_str` to warn on `Md5::from_str`, it hashes `s` as before
+    ///
+    #[deprecated(since = "0.1.1", note = "hashes the text, use `Md5::hash_str`, `FromStr` parses a hex digest from 0.2.0 on")]
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Self, Box<dyn Error>> {
+        <Self as FromStr>::from_str(s)
+    }
+
+    ///
+    /// parse the hex digest printed by `Display`, case insensitive,
+    /// the length is checked first, in chars, then the chars, the index of `InvalidChar` is a char index
+    ///
+    pub fn from_hex(s: &str) -> Result<Self, ParseError> {
+        let len = s.chars().count();
+        if 32 != len {
+            return Err(ParseError::InvalidLength(len));
+        }
+
+        // the bytes before the first non ascii one are one char each, so the byte index is the char index
+        let bytes = s.as_bytes();
+
+        let mut out = [0x00; 16];
+        for (i, c) in bytes.iter().enumerate() {
+            let nibble = match c {
+                b'0'..=b'9' => c - b'0',
+                b'a'..=b'f' => c - b'a' + 10,
+                b'A'..=b'F' => c - b'A' + 10,
+                _ => return Err(ParseError::InvalidChar(i)),
+            };
+            out[i / 2] |= nibble << (4 * (1 - i % 2));
+        }
+
+        Ok(Md5(out))
+    }
+
+    pub fn hash_str(s: &str) -> Self {
+        Self::from_bytes(s.as_bytes())
+    }
+
+    ///
+    /// hash the stream chunk by chunk until EOF
+    ///
+    pub fn from_reader<R: Read>(mut reader: R) -> std::io::Result<Self> {
+        let mut hasher = Md5Hasher::new();
+        let mut chunk = [0x00; 8192];
+
+        loop {
+            match reader.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(n) => hasher.update(&chunk[..n]),
+                Err(e) if ErrorKind::Interrupted == e.kind() => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(hasher.finalize())
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Md5(imp::calc(bytes))
+    }
+
     ///
     /// append/padded/extended in place
     ///
@@ -32,6 +126,203 @@ impl Md5 {
     }
 }
 
+///
+/// incremental md5, feed bytes by `update` and get the digest by `finalize`
+///
+#[derive(Clone, Debug)]
+pub struct Md5Hasher {
+    state: [u32; 4],
+    block: [u8; 64],
+    block_len: usize,
+    bytes_count: u64,
+}
+
+impl Default for Md5Hasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Md5Hasher {
+    pub fn new() -> Self {
+        Self {
+            state: imp::INIT,
+            block: [0; 64],
+            block_len: 0,
+            bytes_count: 0,
+        }
+    }
+
+    pub fn update(&mut self, mut bytes: &[u8]) {
+        self.bytes_count = self.bytes_count.wrapping_add(bytes.len() as u64);
+
+        if 0 < self.block_len {
+            let n = bytes.len().min(64 - self.block_len);
+            self.block[self.block_len..self.block_len + n].copy_from_slice(&bytes[..n]);
+            self.block_len += n;
+            bytes = &bytes[n..];
+
+            if 64 > self.block_len {
+                return;
+            }
+
+            imp::compress(&mut self.state, &self.block);
+            self.block_len = 0;
+        }
+
+        let mut blocks = bytes.chunks_exact(64);
+        for x in blocks.by_ref() {
+            imp::compress(&mut self.state, x.try_into().unwrap());
+        }
+
+        let rest = blocks.remainder();
+        self.block[..rest.len()].copy_from_slice(rest);
+        self.block_len = rest.len();
+    }
+
+    pub fn finalize(mut self) -> Md5 {
+        let bits_count = self.bytes_count.wrapping_mul(u8::BITS as u64);
+
+        let mut padding = [0x00; 64];
+        padding[0] = 0x80;
+        let padding_len = if 56 > self.block_len { 56 - self.block_len } else { 120 - self.block_len };
+
+        self.update(&padding[..padding_len]);
+        self.update(&bits_count.to_le_bytes());
+
+        Md5(imp::to_bytes(&self.state))
+    }
+}
+
+///
+/// hmac md5, see rfc2104
+///
+#[derive(Clone)]
+pub struct HmacMd5 {
+    inner: Md5Hasher,
+    outer: Md5Hasher,
+}
+
+///
+/// redacted, the states after the padded key are as good as the key
+///
+impl Debug for HmacMd5 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HmacMd5").finish_non_exhaustive()
+    }
+}
+
+impl HmacMd5 {
+    pub fn new(key: &[u8]) -> Self {
+        let mut k = [0x00; 64];
+        if 64 < key.len() {
+            k[..16].copy_from_slice(&Md5::from_bytes(key).0);
+        } else {
+            k[..key.len()].copy_from_slice(key);
+        }
+
+        let mut inner = Md5Hasher::new();
+        inner.update(&k.map(|v| v ^ 0x36));
+
+        let mut outer = Md5Hasher::new();
+        outer.update(&k.map(|v| v ^ 0x5c));
+
+        Self { inner, outer }
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.inner.update(bytes);
+    }
+
+    pub fn finalize(mut self) -> Md5 {
+        self.outer.update(&self.inner.finalize().0);
+        self.outer.finalize()
+    }
+
+    ///
+    /// compare with `tag` in constant time
+    ///
+    pub fn verify(self, tag: &[u8]) -> bool {
+        let mac = self.finalize();
+        if 16 != tag.len() {
+            return false;
+        }
+
+        0 == mac.0.iter().zip(tag.iter()).fold(0x00, |diff, (a, b)| diff | (a ^ b))
+    }
+}
+
+///
+/// the first 8 bytes of the digest, little endian
+///
+impl Hasher for Md5Hasher {
+    fn finish(&self) -> u64 {
+        let md5 = self.clone().finalize();
+        u64::from_le_bytes(md5.0[..8].try_into().unwrap())
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+}
+
+pub type BuildMd5 = BuildHasherDefault<Md5Hasher>;
+
+#[cfg(feature = "digest")]
+mod digest_impl {
+    use digest::consts::U16;
+    use digest::FixedOutput;
+    use digest::FixedOutputReset;
+    use digest::HashMarker;
+    use digest::Output;
+    use digest::OutputSizeUser;
+    use digest::Reset;
+    use digest::Update;
+
+    use super::Md5Hasher;
+
+    impl HashMarker for Md5Hasher {}
+
+    impl OutputSizeUser for Md5Hasher {
+        type OutputSize = U16;
+    }
+
+    impl Update for Md5Hasher {
+        fn update(&mut self, data: &[u8]) {
+            Md5Hasher::update(self, data);
+        }
+    }
+
+    impl FixedOutput for Md5Hasher {
+        fn finalize_into(self, out: &mut Output<Self>) {
+            out.copy_from_slice(&Md5Hasher::finalize(self).0);
+        }
+    }
+
+    impl Reset for Md5Hasher {
+        fn reset(&mut self) {
+            *self = Self::new();
+        }
+    }
+
+    impl FixedOutputReset for Md5Hasher {
+        fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+            out.copy_from_slice(&std::mem::take(self).finalize().0);
+        }
+    }
+}
+
+impl Write for Md5Hasher {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
 mod imp {
     use std::ptr::read_unaligned;
 
@@ -134,6 +425,8 @@ mod imp {
         a.wrapping_add(i(b, c, d)).wrapping_add(xi).wrapping_add(ti).rotate_left(si).wrapping_add(b)
     }
 
+    pub const INIT: [u32; 4] = [A, B, C, D];
+
     pub fn calc_in_place(bytes: &mut Vec<u8>) -> [u8; 16] {
         let bytes_count = bytes.len();
         let bits_count = bytes_count as u128 * u8::BITS as u128;
@@ -143,41 +436,68 @@ mod imp {
             bytes.push(0x00);
         }
 
-        bytes.append(&mut Into::<Vec<_>>::into(&bits_count.to_le_bytes()[0..8]));
-
-        let mut aa = A;
-        let mut bb = B;
-        let mut cc = C;
-        let mut dd = D;
-
-        for x in bytes.chunks(64) {
-            let mut a = aa;
-            let mut b = bb;
-            let mut c = cc;
-            let mut d = dd;
-
-            for i in 0..64 {
-                let xi = unsafe { read_unaligned((x as *const [u8] as *mut u32).add(KI[i])).to_le() };
-                if 16 > i {
-                    (a, b, c, d) = (d, ff(a, b, c, d, xi, TI[i], SI[i]), b, c);
-                } else if 32 > i {
-                    (a, b, c, d) = (d, gg(a, b, c, d, xi, TI[i], SI[i]), b, c);
-                } else if 48 > i {
-                    (a, b, c, d) = (d, hh(a, b, c, d, xi, TI[i], SI[i]), b, c);
-                } else {
-                    (a, b, c, d) = (d, ii(a, b, c, d, xi, TI[i], SI[i]), b, c);
-                }
-            }
+        bytes.extend_from_slice(&bits_count.to_le_bytes()[0..8]);
+
+        let mut state = INIT;
 
-            aa = aa.wrapping_add(a);
-            bb = bb.wrapping_add(b);
-            cc = cc.wrapping_add(c);
-            dd = dd.wrapping_add(d);
+        for x in bytes.chunks_exact(64) {
+            compress(&mut state, x.try_into().unwrap());
         }
 
         bytes.truncate(bytes_count);
 
-        let mut all = aa.to_le_bytes().into_iter().chain(bb.to_le_bytes().into_iter()).chain(cc.to_le_bytes().into_iter()).chain(dd.to_le_bytes().into_iter());
+        to_bytes(&state)
+    }
+
+    pub fn calc(bytes: &[u8]) -> [u8; 16] {
+        let bits_count = (bytes.len() as u64).wrapping_mul(u8::BITS as u64);
+
+        let mut state = INIT;
+
+        let mut blocks = bytes.chunks_exact(64);
+        for x in blocks.by_ref() {
+            compress(&mut state, x.try_into().unwrap());
+        }
+
+        let rest = blocks.remainder();
+        let mut tail = [0x00; 128];
+        tail[..rest.len()].copy_from_slice(rest);
+        tail[rest.len()] = 0x80;
+
+        let tail_len = if 56 > rest.len() { 64 } else { 128 };
+        tail[tail_len - 8..tail_len].copy_from_slice(&bits_count.to_le_bytes());
+
+        for x in tail[..tail_len].chunks_exact(64) {
+            compress(&mut state, x.try_into().unwrap());
+        }
+
+        to_bytes(&state)
+    }
+
+    pub fn compress(state: &mut [u32; 4], x: &[u8; 64]) {
+        let [mut a, mut b, mut c, mut d] = *state;
+
+        for i in 0..64 {
+            let xi = unsafe { read_unaligned((x as *const [u8; 64] as *const u32).add(KI[i])).to_le() };
+            if 16 > i {
+                (a, b, c, d) = (d, ff(a, b, c, d, xi, TI[i], SI[i]), b, c);
+            } else if 32 > i {
+                (a, b, c, d) = (d, gg(a, b, c, d, xi, TI[i], SI[i]), b, c);
+            } else if 48 > i {
+                (a, b, c, d) = (d, hh(a, b, c, d, xi, TI[i], SI[i]), b, c);
+            } else {
+                (a, b, c, d) = (d, ii(a, b, c, d, xi, TI[i], SI[i]), b, c);
+            }
+        }
+
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+    }
+
+    pub fn to_bytes(state: &[u32; 4]) -> [u8; 16] {
+        let mut all = state.iter().flat_map(|v| v.to_le_bytes());
 
         std::array::from_fn(|_| all.next().unwrap())
     }
@@ -189,8 +509,147 @@ mod tests {
 
     #[test]
     fn it_works() {
-        assert_eq!(Md5::from_str("juxt_md5").unwrap().to_string(), "32f3f7648da7a812e8bcac55822c25bb");
-        assert_eq!(Md5::from_str("juxt_md5").unwrap(), Md5::from_str("juxt_md5").unwrap().clone());
-        assert_eq!(format!("{:02x?}", Md5::from_str("juxt_md5").unwrap()), "Md5([32, f3, f7, 64, 8d, a7, a8, 12, e8, bc, ac, 55, 82, 2c, 25, bb])");
+        assert_eq!("juxt_md5".parse::<Md5>().unwrap().to_string(), "32f3f7648da7a812e8bcac55822c25bb");
+        assert_eq!("juxt_md5".parse::<Md5>().unwrap(), "juxt_md5".parse::<Md5>().unwrap().clone());
+        assert_eq!(format!("{:02x?}", "juxt_md5".parse::<Md5>().unwrap()), "Md5([32, f3, f7, 64, 8d, a7, a8, 12, e8, bc, ac, 55, 82, 2c, 25, bb])");
+
+        #[allow(deprecated)]
+        let md5 = Md5::from_str("juxt_md5").unwrap();
+        assert_eq!(md5, Md5::hash_str("juxt_md5"));
+    }
+
+    #[test]
+    fn from_hex_works() {
+        let md5 = Md5::hash_str("juxt_md5");
+        assert_eq!(Md5::from_hex(&md5.to_string()), Ok(md5));
+        assert_eq!(Md5::from_hex("32F3F7648DA7A812E8BCAC55822C25BB"), Ok(md5));
+        assert_eq!(<Md5 as FromStr>::from_str("juxt_md5").unwrap(), md5);
+
+        assert_eq!(Md5::from_hex(""), Err(ParseError::InvalidLength(0)));
+        assert_eq!(Md5::from_hex("32f3f7648da7a812e8bcac55822c25bb0"), Err(ParseError::InvalidLength(33)));
+        assert_eq!(Md5::from_hex("32f3f7648da7a812e8bcac55822c25bg"), Err(ParseError::InvalidChar(31)));
+        assert_eq!(Md5::from_hex("32f3f7648da7a812e8bcac55822c25b\u{e9}").unwrap_err().to_string(), "invalid md5 hex char at index 31");
+        assert_eq!(Md5::from_hex("32f3f764\u{e9}da7a812e8bcac55822c25bb"), Err(ParseError::InvalidChar(8)));
+
+        // the length first, the same for ascii and non ascii
+        assert_eq!(Md5::from_hex("\u{e9}00"), Err(ParseError::InvalidLength(3)));
+        assert_eq!(Md5::from_hex("x00"), Err(ParseError::InvalidLength(3)));
+    }
+
+    #[test]
+    fn from_bytes_works() {
+        #[rustfmt::skip]
+        let cases = [
+            ("", "d41d8cd98f00b204e9800998ecf8427e"),
+            ("a", "0cc175b9c0f1b6a831c399e269772661"),
+            ("abc", "900150983cd24fb0d6963f7d28e17f72"),
+            ("message digest", "f96b697d7cb7938d525a2f31aaf161d0"),
+            ("abcdefghijklmnopqrstuvwxyz", "c3fcd3d76192e4007dfb496cca67e13b"),
+            ("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789", "d174ab98d277d9f5a5611c2c9f419d9f"),
+            ("12345678901234567890123456789012345678901234567890123456789012345678901234567890", "57edf4a22be3c955ac49da2e2107b67a"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(Md5::from_bytes(input.as_bytes()).to_string(), expected);
+        }
+
+        let bytes: Vec<u8> = (0..300u32).map(|v| (v * 7 + 3) as u8).collect();
+        for len in 0..bytes.len() {
+            assert_eq!(Md5::from_bytes(&bytes[..len]), Md5::from_vec(&mut bytes[..len].to_vec()));
+        }
+    }
+
+    #[test]
+    fn hasher_works() {
+        let bytes: Vec<u8> = (0..300u32).map(|v| (v * 7 + 3) as u8).collect();
+
+        for len in [0, 1, 55, 56, 63, 64, 65, 119, 120, 128, 300] {
+            let expected = Md5::from_vec(&mut bytes[..len].to_vec());
+            for chunk_size in 1..=130 {
+                let mut hasher = Md5Hasher::new();
+                bytes[..len].chunks(chunk_size).for_each(|chunk| hasher.update(chunk));
+                assert_eq!(hasher.finalize(), expected);
+            }
+        }
+
+        let mut hasher = Md5Hasher::default();
+        hasher.update(b"juxt_");
+        hasher.update(b"md5");
+        assert_eq!(hasher.finalize().to_string(), "32f3f7648da7a812e8bcac55822c25bb");
+    }
+
+    #[test]
+    fn hmac_works() {
+        #[rustfmt::skip]
+        let cases: [(Vec<u8>, Vec<u8>, &str); 7] = [
+            (vec![0x0b; 16], b"Hi There".to_vec(), "9294727a3638bb1c13f48ef8158bfc9d"),
+            (b"Jefe".to_vec(), b"what do ya want for nothing?".to_vec(), "750c783e6ab0b503eaa86e310a5db738"),
+            (vec![0xaa; 16], vec![0xdd; 50], "56be34521d144c88dbb8c733f0e8b3f6"),
+            ((0x01..=0x19).collect(), vec![0xcd; 50], "697eaf0aca3a3aea3a75164746ffaa79"),
+            (vec![0x0c; 16], b"Test With Truncation".to_vec(), "56461ef2342edc00f9bab995690efd4c"),
+            (vec![0xaa; 80], b"Test Using Larger Than Block-Size Key - Hash Key First".to_vec(), "6b1ab7fe4bd7bf8f0b62e6ce61b9d0cd"),
+            (vec![0xaa; 80], b"Test Using Larger Than Block-Size Key and Larger Than One Block-Size Data".to_vec(), "6f630fad67cda0ee1fb1f562db3aa53e"),
+        ];
+        for (key, data, expected) in cases {
+            let mut hmac = HmacMd5::new(&key);
+            data.chunks(7).for_each(|chunk| hmac.update(chunk));
+            assert_eq!(hmac.clone().finalize().to_string(), expected);
+
+            let tag = Md5::from_hex(expected).unwrap().0;
+            assert!(hmac.clone().verify(&tag));
+            assert!(!hmac.clone().verify(&tag[..15]));
+            let mut bad = tag;
+            bad[15] ^= 0x01;
+            assert!(!hmac.verify(&bad));
+        }
+
+        assert_eq!(format!("{:?}", HmacMd5::new(b"Jefe")), "HmacMd5 { .. }");
+    }
+
+    #[test]
+    fn std_hasher_works() {
+        use std::collections::HashMap;
+        use std::hash::BuildHasher;
+
+        let mut hasher = Md5Hasher::new();
+        Hasher::write(&mut hasher, b"juxt_md5");
+        assert_eq!(hasher.finish(), 0x12a8a78d64f7f332);
+        assert_eq!(hasher.finish(), 0x12a8a78d64f7f332);
+
+        let build = BuildMd5::default();
+        assert_eq!(build.hash_one("juxt_md5"), build.hash_one("juxt_md5"));
+
+        let mut map = HashMap::with_hasher(BuildMd5::default());
+        map.insert("juxt", 1);
+        map.insert("md5", 2);
+        assert_eq!(map["juxt"], 1);
+        assert_eq!(map["md5"], 2);
+    }
+
+    #[cfg(feature = "digest")]
+    #[test]
+    fn digest_works() {
+        use digest::Digest;
+
+        let expected = Md5::hash_str("juxt_md5").0;
+
+        let mut hasher = <Md5Hasher as Digest>::new();
+        Digest::update(&mut hasher, b"juxt_");
+        Digest::update(&mut hasher, b"md5");
+        assert_eq!(hasher.finalize_reset()[..], expected);
+        assert_eq!(Digest::finalize(hasher)[..], Md5::from_bytes(b"").0);
+        assert_eq!(<Md5Hasher as Digest>::digest(b"juxt_md5")[..], expected);
+    }
+
+    #[test]
+    fn io_works() {
+        let bytes: Vec<u8> = (0..20000u32).map(|v| (v * 7 + 3) as u8).collect();
+        let expected = Md5::from_bytes(&bytes);
+
+        assert_eq!(Md5::from_reader(&bytes[..]).unwrap(), expected);
+        assert_eq!(Md5::from_reader(std::io::empty()).unwrap(), Md5::from_bytes(b""));
+
+        let mut hasher = Md5Hasher::new();
+        assert_eq!(std::io::copy(&mut &bytes[..], &mut hasher).unwrap(), bytes.len() as u64);
+        assert_eq!(hasher.finalize(), expected);
     }
 }